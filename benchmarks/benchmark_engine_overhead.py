@@ -0,0 +1,48 @@
+import pytest
+
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+
+from tests.test_cases import benchmark_model
+
+TIMESTEPS = 100_000
+RUNS = 1
+
+# The engine overhead is measured using a model of the same shape as the benchmark model,
+# whose Policy and State Update Functions do no work, to compare against the cost of the model itself
+
+def policy_noop(params, substep, state_history, previous_state):
+    return {'step_size': 1}
+
+def update_a_noop(params, substep, state_history, previous_state, policy_input):
+    return 'a', 1.0
+
+def update_b_noop(params, substep, state_history, previous_state, policy_input):
+    return 'b', 1.0
+
+noop_state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'a': update_a_noop}
+    },
+    {
+        'policies': {f'p_{index}': policy_noop for index in range(1, 6)},
+        'variables': {'b': update_b_noop}
+    },
+]
+
+engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+noop_model = Model(initial_state=benchmark_model.states, state_update_blocks=noop_state_update_blocks, params={})
+noop_experiment = Experiment([Simulation(model=noop_model, timesteps=TIMESTEPS, runs=RUNS)])
+noop_experiment.engine = engine
+
+model = Model(initial_state=benchmark_model.states, state_update_blocks=benchmark_model.state_update_blocks, params={'a': 1, 'b': 1})
+experiment = Experiment([Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS)])
+experiment.engine = engine
+
+def test_benchmark_engine_overhead(benchmark):
+    benchmark.pedantic(noop_experiment.run, iterations=1, rounds=3)
+
+def test_benchmark_engine_with_model(benchmark):
+    benchmark.pedantic(experiment.run, iterations=1, rounds=3)
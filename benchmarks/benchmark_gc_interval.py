@@ -0,0 +1,37 @@
+import pytest
+
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+
+from tests.test_cases import benchmark_model
+
+states = benchmark_model.states
+state_update_blocks = benchmark_model.state_update_blocks
+params = benchmark_model.params
+TIMESTEPS = 100_000
+RUNS = 3
+
+model = Model(initial_state=states, state_update_blocks=state_update_blocks, params=params)
+simulation_radcad = Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS)
+experiment = Experiment([simulation_radcad])
+
+def test_benchmark_radcad_default_gc(benchmark):
+    benchmark.pedantic(radcad_default_gc_simulation, iterations=1, rounds=3)
+
+def radcad_default_gc_simulation():
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    data_radcad = experiment.run()
+
+def test_benchmark_radcad_gc_every_substep(benchmark):
+    benchmark.pedantic(radcad_gc_every_substep_simulation, iterations=1, rounds=3)
+
+def radcad_gc_every_substep_simulation():
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, gc_interval=1)
+    data_radcad = experiment.run()
+
+def test_benchmark_radcad_gc_every_1000_substeps(benchmark):
+    benchmark.pedantic(radcad_gc_every_1000_substeps_simulation, iterations=1, rounds=3)
+
+def radcad_gc_every_1000_substeps_simulation():
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, gc_interval=1000)
+    data_radcad = experiment.run()
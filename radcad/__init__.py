@@ -1,5 +1,7 @@
 __version__ = "0.9.0"
 
-from radcad.wrappers import Context, Model, Simulation, Experiment
-from radcad.engine import Engine
+from radcad.wrappers import Context, Model, Simulation, SimulationStepper, Experiment
+from radcad.engine import Engine, set_pre_run_hook, set_post_run_hook
 from radcad.backends import Backend
+from radcad.utils import safe_div
+from radcad.core import Scenarios
@@ -8,6 +8,7 @@ class Backend(Enum):
     RAY_REMOTE = 3
     PATHOS = 4
     SINGLE_PROCESS = 5
+    THREADS = 6
 
 class Executor(object):
     def __init__(self, engine):
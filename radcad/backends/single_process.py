@@ -4,8 +4,9 @@ import radcad.core as core
 
 class ExecutorSingleProcess(Executor):
     def execute_runs(self):
-        result = [
+        result = (
             core._single_run_wrapper((config, self.engine.raise_exceptions))
             for config in self.engine._run_generator
-        ]
-        return result
+        )
+        # When spilling results to disk, runs are executed lazily as the results are collected, so that they're spilled one at a time
+        return result if self.engine._lazy_results else list(result)
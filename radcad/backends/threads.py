@@ -0,0 +1,18 @@
+from radcad.backends import Executor
+import radcad.core as core
+
+from concurrent.futures import ThreadPoolExecutor
+
+
+class ExecutorThreads(Executor):
+    def execute_runs(self):
+        # Threads share the calling process, so Policy and State Update Functions don't need to be picklable
+        with ThreadPoolExecutor(max_workers=self.engine.processes) as pool:
+            result = list(pool.map(
+                core._single_run_wrapper,
+                [
+                    (config, self.engine.raise_exceptions)
+                    for config in self.engine._run_generator
+                ],
+            ))
+        return result
@@ -1,18 +1,455 @@
+from collections.abc import Iterator, MappingView, Set
 from functools import reduce, partial
+import contextlib
+import copy
+import gc
+import inspect
+import io
 import logging
+import math
+import numbers
+import os
 import pickle
+import random
+import sys
+import time
 import traceback
-from typing import Dict, List, Tuple
+import uuid
+import warnings
+from dataclasses import dataclass
+from types import MappingProxyType
+from typing import Any, Callable, Dict, Generator, List, Tuple, Union
 
+from radcad.serializers import PickleSerializer
 
-def _update_state(initial_state, params, substep, result, substate, signals, state_update_tuple):
+try:
+    import numpy
+except ImportError:
+    numpy = None
+
+
+_IMMUTABLE_TYPES = (int, float, complex, bool, str, bytes, type(None))
+
+
+def _is_flat_dict_list(value) -> bool:
+    return type(value) is list and all(
+        type(item) is dict and all(type(v) in _IMMUTABLE_TYPES for v in item.values())
+        for item in value
+    )
+
+
+_default_serializer = PickleSerializer()
+
+
+def _deepcopy_state(state: dict, fast_list_copy: bool=False, serializer=None, deepcopy_keys=None) -> dict:
+    serializer = serializer or _default_serializer
+    if deepcopy_keys is not None:
+        # Only the declared keys are deep copied, and other State Variables, treated as immutable, are referenced
+        copied = _deepcopy_state({key: state[key] for key in deepcopy_keys if key in state}, fast_list_copy, serializer)
+        return {key: copied[key] if key in copied else value for key, value in state.items()}
+    if not fast_list_copy:
+        return serializer.copy(state)
+
+    # Lists of dicts with immutable values (e.g. agents) only need each dict to be copied
+    fast_copied = {key: [item.copy() for item in value] for key, value in state.items() if _is_flat_dict_list(value)}
+    copied = serializer.copy({key: value for key, value in state.items() if key not in fast_copied})
+    return {key: fast_copied[key] if key in fast_copied else copied[key] for key in state}
+
+
+class SubstepHookError(Exception):
+    """
+    Raised when the `after_substep` hook raises an exception, identifying the substep.
+    """
+
+    def __init__(self, simulation: int, run: int, subset: int, timestep: int, substep: int):
+        self.simulation = simulation
+        self.run = run
+        self.subset = subset
+        self.timestep = timestep
+        self.substep = substep
+        super().__init__(
+            f"after_substep hook failed at simulation {simulation} / run {run} / subset {subset} / timestep {timestep} / substep {substep}"
+        )
+
+
+RUN_UUID_NAMESPACE = uuid.UUID("9f1c7a5e-2d4b-5c3e-8a6f-0b7d1e2c3a4f")
+
+
+def generate_run_uuid(simulation: int, run: int, subset: int, seed: int = None) -> str:
+    # Runs with different seeds are different trajectories, so the seed, when set, is part of the name
+    name = f"{simulation}/{run}/{subset}" if seed is None else f"{simulation}/{run}/{subset}/{seed}"
+    return str(uuid.uuid5(RUN_UUID_NAMESPACE, name))
+
+
+def derive_run_seed(seed: int, simulation: int, run: int, subset: int) -> int:
+    # Tuples of integers hash deterministically, unlike strings, so the seed is the same in every process
+    return hash((seed, simulation, run, subset)) % 2**32
+
+
+def derive_substep_seed(run_seed: int, timestep: int, substep: int) -> int:
+    return hash((run_seed, timestep, substep)) % 2**32
+
+
+def _seed_random(run_seed: int):
+    random.seed(run_seed)
+    if numpy is not None:
+        numpy.random.seed(run_seed)
+
+
+def _pin_to_core():
+    # Pool worker processes are numbered from 1, and each pinned to one of the available cores in turn,
+    # while the calling process, e.g. when using a single process backend, isn't pinned
+    identities = [
+        sys.modules[module].current_process()._identity
+        for module in ["multiprocessing", "multiprocess"] if module in sys.modules
+    ]
+    identity = next((identity for identity in identities if identity), None)
+    if identity:
+        cores = sorted(os.sched_getaffinity(0))
+        os.sched_setaffinity(0, {cores[(identity[0] - 1) % len(cores)]})
+
+
+def memory_usage_mb() -> float:
+    """
+    Returns the resident memory of the current process in MB, or its peak resident memory where the current isn't available.
+    """
+    try:
+        with open("/proc/self/statm") as statm:
+            return int(statm.read().split()[1]) * os.sysconf("SC_PAGE_SIZE") / 2**20
+    except (OSError, ValueError):
+        import resource
+        peak = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss
+        # Reported in bytes on macOS, and kilobytes elsewhere
+        return peak / 2**20 if sys.platform == "darwin" else peak / 2**10
+
+
+def check_memory_budget(max_memory_mb: float, location: str):
+    usage = memory_usage_mb()
+    if usage > max_memory_mb:
+        raise MemoryError(
+            f"Process memory of {usage:.0f} MB exceeds the memory budget of {max_memory_mb} MB {location}"
+        )
+
+
+def _check_finite(substate: dict, keys, check_finite):
+    for key in keys:
+        value = substate[key]
+        if isinstance(value, numbers.Real) and not isinstance(value, bool) and not math.isfinite(value):
+            message = f"Non-finite value {value} for state variable {key} at timestep {substate['timestep']} / substep {substate['substep']}"
+            if check_finite == "warn":
+                logging.warning(message)
+            else:
+                raise ValueError(message)
+
+
+def _check_signal_finite(signals: dict, substep: int, timestep: int):
+    for (key, value) in signals.items():
+        if isinstance(value, numbers.Real) and not isinstance(value, bool) and not math.isfinite(value):
+            raise ValueError(f"Non-finite value {value} for policy signal {key} at timestep {timestep} / substep {substep + 1}")
+
+
+def _check_types(substate: dict, keys, state_schema: dict):
+    # A schema entry is either a type, or tuple of types, or a collection of allowed categorical values
+    for key in keys:
+        if key not in state_schema:
+            continue
+        expected, value = state_schema[key], substate[key]
+        location = f"at timestep {substate.get('timestep', 0)} / substep {substate.get('substep', 0)}"
+        if isinstance(expected, (type, tuple)):
+            types = expected if isinstance(expected, tuple) else (expected,)
+            if isinstance(value, bool) and bool not in types:
+                # Booleans aren't conflated with integers, unless bool is declared
+                valid = False
+            else:
+                # Integers are accepted where floats are declared
+                valid = isinstance(value, types) or (float in types and isinstance(value, numbers.Integral))
+            if not valid:
+                raise TypeError(
+                    f"Invalid type {type(value).__name__} for state variable {key} {location}, "
+                    f"expected {' or '.join(t.__name__ for t in types)}"
+                )
+        elif value not in expected:
+            raise ValueError(f"Invalid value {value!r} for state variable {key} {location}, expected one of {sorted(expected, key=repr)}")
+
+
+BOOKKEEPING_KEYS = ["simulation", "subset", "run", "run_uuid", "substep", "substep_label", "iteration", "timestep", "wall_time", "__updated__"]
+DEFAULT_MAX_ITERATIONS = 100
+WALL_CLOCKS = {"epoch": time.time, "monotonic": time.monotonic}
+# Returned from a Policy Function to stop the remaining substeps of the timestep, when enabled
+STOP = type("Stop", (), {"__repr__": lambda self: "STOP"})()
+# Yielded from a run after each timestep, when stepping a run one timestep at a time
+TIMESTEP_END = type("TimestepEnd", (), {"__repr__": lambda self: "TIMESTEP_END"})()
+# Prefix of the keys of reduced signals recorded with the state, when enabled
+SIGNAL_PREFIX = "signal_"
+# Back-filled in earlier records for State Variables added during a run, when enabled, preserved when copied or pickled
+MISSING = type("Missing", (), {"__repr__": lambda self: "MISSING", "__reduce__": lambda self: "MISSING"})()
+# Running aggregates of the cumulative Simulation option, combining the previous aggregate with the latest value
+CUMULATIVE_AGGREGATES = {"sum": lambda a, b: a + b, "max": max, "min": min}
+# Strategies for a State Variable updated by more than one PSU within a timestep
+DUPLICATE_UPDATES = ["last", "warn", "error", "sum"]
+# Number of timesteps between checks of the process memory against a memory budget
+MEMORY_CHECK_INTERVAL = 10
+# cadCAD Partial State Update Block keys, accepted as aliases of "policies" and "variables"
+PSU_KEY_ALIASES = {"behaviors": "policies", "states": "variables"}
+
+
+def _validate_state(substate: dict, keys, validators: dict, timestep: int, substep: int):
+    # Validators are called with each updated value, returning the value to store, e.g. clamped, or raising to reject it
+    for key in keys:
+        if key in validators:
+            try:
+                substate[key] = validators[key](substate[key])
+            except Exception as error:
+                raise ValueError(
+                    f"Validation of state variable {key} failed at timestep {timestep} / substep {substep + 1}: {error}"
+                ) from error
+
+
+def _check_signals(signals: dict, expected_signals, substep: int, timestep: int):
+    missing_keys, unexpected_keys = set(expected_signals) - signals.keys(), signals.keys() - set(expected_signals)
+    if missing_keys or unexpected_keys:
+        warnings.warn(
+            f"Policy signals of substep {substep + 1} at timestep {timestep} don't match the expected signals: "
+            f"missing {sorted(missing_keys)}, unexpected {sorted(unexpected_keys)}"
+        )
+
+
+def _resolve_duplicate_updates(substate: dict, updates, updated_by: dict, substep: int, timestep: int, duplicate_updates: str) -> list:
+    # State Variables updated by more than one PSU within a timestep are resolved according to the duplicate_updates option
+    resolved = []
+    for (key, value) in updates:
+        if updated_by.get(key, substep) != substep:
+            message = f"State Variable {key} updated by substeps {updated_by[key] + 1} and {substep + 1} of timestep {timestep}"
+            if duplicate_updates == "error":
+                raise ValueError(message)
+            elif duplicate_updates == "warn":
+                warnings.warn(message)
+            elif duplicate_updates == "sum":
+                value = substate[key] + value
+        updated_by[key] = substep
+        resolved.append((key, value))
+    return resolved
+
+
+def _cumulative_columns(initial_state: dict, cumulative: dict) -> list:
+    # Each cumulative State Variable is recorded as a `{key}_cum{aggregate}` column, e.g. `fees_cumsum`
+    columns = []
+    for (key, aggregate) in cumulative.items():
+        if key not in initial_state:
+            raise KeyError(f"Invalid state key {key} in cumulative columns")
+        if aggregate not in CUMULATIVE_AGGREGATES:
+            raise ValueError(f"Cumulative aggregate must be one of {list(CUMULATIVE_AGGREGATES)}, not {aggregate}")
+        columns.append((key, f"{key}_cum{aggregate}", CUMULATIVE_AGGREGATES[aggregate]))
+    return columns
+
+
+def _check_schema(substate: dict, state_keys: set):
+    if substate.keys() != state_keys:
+        raise KeyError(
+            f"State schema changed at timestep {substate['timestep']} / substep {substate['substep']}: "
+            f"added {sorted(substate.keys() - state_keys)}, removed {sorted(state_keys - substate.keys())}"
+        )
+
+
+def _validate_initial_history(initial_state: dict, initial_history: list):
+    state_keys = set(initial_state) - set(BOOKKEEPING_KEYS)
+    for (timestep, substeps) in enumerate(initial_history):
+        for record in substeps:
+            record_keys = set(record) - set(BOOKKEEPING_KEYS)
+            if record_keys != state_keys:
+                raise ValueError(
+                    f"Initial history record at timestep {timestep} doesn't match initial state keys: "
+                    f"missing {sorted(state_keys - record_keys)}, unexpected {sorted(record_keys - state_keys)}"
+                )
+
+
+def _fill_initial_history(initial_history: list, initial_state: dict, run_uuid: bool, deltas: list, cumulative_columns: list):
+    # The run_uuid, delta, and cumulative columns of the initial history are filled as if it had been simulated,
+    # and the initial state continues the deltas and cumulative aggregates from the final record of the initial history
+    previous_state = None
+    for substeps in initial_history + [[initial_state]]:
+        for record in substeps:
+            if run_uuid:
+                record["run_uuid"] = initial_state["run_uuid"]
+            for key in deltas or []:
+                record[f"{key}_delta"] = None if previous_state is None else record[key] - previous_state[key]
+            for (key, column, _) in cumulative_columns:
+                record[column] = record[key] if previous_state is None else previous_state[column]
+        for (key, column, aggregate) in cumulative_columns if previous_state is not None else []:
+            substeps[-1][column] = aggregate(previous_state[column], substeps[-1][key])
+        previous_state = substeps[-1]
+
+
+def _traced(tracer, attributes: dict, span: str, name: str, function):
+    def traced_function(*args):
+        tracer("start", span, {**attributes, "name": name})
+        try:
+            function_result = function(*args)
+        except Exception as error:
+            # The span is ended by an "error" event, with the exception raised by the function
+            tracer("error", span, {**attributes, "name": name, "error": error})
+            raise
+        tracer("end", span, {**attributes, "name": name})
+        return function_result
+    return traced_function
+
+
+def _profiled(profile: dict, span: str, name: str, function):
+    def profiled_function(*args):
+        start = time.perf_counter()
+        function_result = function(*args)
+        stats = profile.setdefault(f"{span}:{name}", {"calls": 0, "seconds": 0.0})
+        stats["calls"] += 1
+        stats["seconds"] += time.perf_counter() - start
+        return function_result
+    return profiled_function
+
+
+def _with_scratch(scratch: dict, span: str, name: str, function):
+    def scratch_function(*args):
+        return function(*args, scratch)
+    return scratch_function
+
+
+def _wrap_state_update_blocks(wrapper, state_update_blocks: list):
+    # Wraps each Policy and State Update Function using wrapper(span, name, function)
+    return [
+        {
+            **psu,
+            "policies": {
+                name: wrapper("policy", name, function)
+                for (name, function) in psu["policies"].items()
+            },
+            "variables": {
+                name: wrapper("variable", name, function)
+                for (name, function) in psu["variables"].items()
+            },
+        }
+        for psu in state_update_blocks
+    ]
+
+
+def merge_profiles(profiles: list) -> dict:
+    merged = {}
+    for profile in profiles:
+        for (key, stats) in profile.items():
+            merged_stats = merged.setdefault(key, {"calls": 0, "seconds": 0.0})
+            merged_stats["calls"] += stats["calls"]
+            merged_stats["seconds"] += stats["seconds"]
+    return merged
+
+
+def _lifecycle_objects(state_update_blocks: list) -> list:
+    # Objects that Policy and State Update Functions are bound methods of, in order of first use
+    objects = []
+    for psu in state_update_blocks:
+        for function in [*psu["policies"].values(), *psu["variables"].values()]:
+            function = function.func if isinstance(function, partial) else function
+            if inspect.ismethod(function) and not inspect.isclass(function.__self__):
+                if not any(function.__self__ is obj for obj in objects):
+                    objects.append(function.__self__)
+    return objects
+
+
+@contextlib.contextmanager
+def _setup_teardown(obj):
+    obj.setup()
+    try:
+        yield obj
+    finally:
+        obj.teardown()
+
+
+def _enter_lifecycle_objects(stack: contextlib.ExitStack, state_update_blocks: list):
+    for obj in _lifecycle_objects(state_update_blocks):
+        if hasattr(obj, "__enter__") and hasattr(obj, "__exit__"):
+            stack.enter_context(obj)
+        elif hasattr(obj, "setup") and hasattr(obj, "teardown"):
+            stack.enter_context(_setup_teardown(obj))
+
+
+def _bind_functions(functions: dict) -> dict:
+    # Functions declared as (function, bound_kwargs) tuples are called with the bound keyword arguments,
+    # bound once per run when the state update blocks are normalized, rather than every substep
+    if not isinstance(functions, dict) or not any(isinstance(function, tuple) for function in functions.values()):
+        return functions
+    return {
+        name: partial(function[0], **function[1]) if isinstance(function, tuple) else function
+        for (name, function) in functions.items()
+    }
+
+
+def _normalize_psu(psu: dict) -> dict:
+    # Renames cadCAD aliases of Partial State Update Block keys, unless the radCAD key is also present
+    if any(alias in psu for alias in PSU_KEY_ALIASES):
+        psu = {
+            (PSU_KEY_ALIASES[key] if key in PSU_KEY_ALIASES and PSU_KEY_ALIASES[key] not in psu else key): value
+            for (key, value) in psu.items()
+        }
+    policies, variables = _bind_functions(psu.get("policies")), _bind_functions(psu.get("variables"))
+    if policies is not psu.get("policies") or variables is not psu.get("variables"):
+        psu = {**psu, "policies": policies, "variables": variables}
+    return psu
+
+
+def validate_state_update_blocks(initial_state: dict, state_update_blocks: list):
+    for (substep, psu) in enumerate(map(_normalize_psu, state_update_blocks)):
+        for key in ["policies", "variables"]:
+            if not isinstance(psu.get(key, None), dict):
+                raise KeyError(f"Partial state update block {substep} requires a dict of {key}")
+            for (name, function) in psu[key].items():
+                if not callable(function):
+                    raise TypeError(f"Function {name} in partial state update block {substep} isn't callable")
+        for state in psu["variables"]:
+            if not state in initial_state:
+                raise KeyError(f"Invalid state key {state} in partial state update block")
+        for state in psu.get("lazy", {}):
+            if state not in psu["variables"]:
+                raise KeyError(f"Lazy state key {state} isn't updated by partial state update block {substep}")
+
+
+def _psu_params(params: dict, psu: dict) -> dict:
+    # Parameters seen by the Policy and State Update Functions of a PSU, with any "param_overrides" applied
+    overrides = psu.get("param_overrides", None)
+    if not overrides:
+        return params
+    unknown_keys = set(overrides) - set(params)
+    if unknown_keys:
+        raise KeyError(f"Parameter overrides {sorted(unknown_keys)} in partial state update block aren't parameters")
+    return {**params, **overrides}
+
+
+def _cached_reduce_signals(signal_cache: dict, params: dict, substep: int, result: list, substate: dict, psu: dict, *args):
+    # A "cacheable" PSU reduces its Policy Functions once per timestep, reusing the signals when the substep is re-entered
+    if not psu.get("cacheable", False):
+        return _reduce_signals(params, substep, result, substate, psu, *args)
+    if substep not in signal_cache:
+        signal_cache[substep] = _reduce_signals(params, substep, result, substate, psu, *args)
+    return signal_cache[substep]
+
+
+def _changed_variables(variables, lazy: dict, signals: dict, lazy_signals: dict, substep: int) -> list:
+    # A "lazy" State Update Function is skipped, keeping the prior value, while the signals it depends on
+    # are identical to the signals it was last called with
+    changed = []
+    for (key, function) in variables:
+        if key in lazy:
+            dependencies = {signal: signals.get(signal, MISSING) for signal in lazy[key]}
+            if lazy_signals.get((substep, key), None) == dependencies:
+                continue
+            lazy_signals[(substep, key)] = dependencies
+        changed.append((key, function))
+    return changed
+
+
+def _update_state(initial_state, params, substep, result, substate, signals, state_update_tuple, allow_new_keys=False):
     state, function = state_update_tuple
-    if not state in initial_state:
+    if not state in initial_state and not allow_new_keys:
         raise KeyError(f"Invalid state key {state} in partial state update block")
     state_key, state_value = function(
         params, substep, result, substate, signals
     )
-    if not state_key in initial_state:
+    if not state_key in initial_state and not allow_new_keys:
         raise KeyError(
             f"Invalid state key {state} returned from state update function"
         )
@@ -24,6 +461,65 @@ def _update_state(initial_state, params, substep, result, substate, signals, sta
         )
 
 
+def _final_records(records: list, passed: list, final_only: bool) -> list:
+    # The final state of a run is always recorded, along with any states of the final timestep passing record_when
+    if final_only or passed is None:
+        return records[-1:] if final_only else records
+    return [record for record in passed if record is not records[-1]] + records[-1:]
+
+
+@dataclass
+class RunOptions:
+    """
+    The Engine and Simulation options of a single run, set by keyword so that each option keeps its own default.
+    See the `Engine` and `Simulation` options of the same name.
+    """
+    final_only: bool = False
+    fast_list_copy: bool = False
+    run_uuid: bool = False
+    check_finite: Union[bool, str] = False
+    serializer: Any = None
+    initial_history: list = None
+    tracer: Callable = None
+    check_policy_mutation: bool = False
+    record_wall_time: Union[bool, str] = False
+    profile: bool = False
+    initial_substep: int = 0
+    strict_schema: bool = False
+    after_substep: Callable = None
+    capture_stdout: bool = False
+    start_timestep: int = 0
+    run_metadata: dict = None
+    record_stride: int = None
+    record_updated: bool = False
+    one_based_run: bool = None
+    allow_stop: bool = False
+    state_schema: dict = None
+    policies_only: bool = False
+    allow_new_keys: bool = False
+    seed: int = None
+    on_run_finished: Callable = None
+    gc_interval: int = None
+    substeps_per_timestep: int = None
+    frozen_state: bool = False
+    record_signals: bool = False
+    pin_cores: bool = False
+    # Wall-clock time after which the run is interrupted between timesteps
+    deadline: float = None
+    validators: dict = None
+    terminating_condition: Callable = None
+    scratch: bool = False
+    record_when: Callable = None
+    duplicate_updates: str = "last"
+    cumulative: dict = None
+    check_signal_finite: bool = False
+    seed_substeps: bool = False
+    deepcopy_keys: list = None
+    deltas: list = None
+    max_memory_mb: float = None
+    manage_lifecycle: bool = False
+
+
 def _single_run(
     result: list,
     simulation: int,
@@ -35,53 +531,373 @@ def _single_run(
     params: dict,
     deepcopy: bool,
     drop_substeps: bool,
+    options: RunOptions,
+    metadata: dict,
+    step_timesteps: bool,
 ):
     logging.info(f"Starting simulation {simulation} / run {run} / subset {subset}")
+    # The run index recorded in the state is one-based unless `one_based_run` is disabled
+    run_index = run if options.one_based_run is False else run + 1
+
+    if options.initial_history:
+        _validate_initial_history(initial_state, options.initial_history)
+        for substeps in options.initial_history:
+            for record in substeps:
+                record.update({"simulation": simulation, "subset": subset, "run": run_index, **(options.run_metadata or {})})
+        result.extend(options.initial_history)
+        # Timestep indexing continues from the end of the initial history
+        initial_state["timestep"] = options.initial_history[-1][-1].get("timestep", len(options.initial_history) - 1) + 1
 
+    if options.deepcopy_keys is not None and set(options.deepcopy_keys) - set(initial_state):
+        raise KeyError(f"Invalid state keys {sorted(set(options.deepcopy_keys) - set(initial_state))} in deepcopy keys")
+    if options.cumulative:
+        # Cumulative columns are seeded with the initial value of their State Variable
+        cumulative_columns = _cumulative_columns(initial_state, options.cumulative)
+        for (key, column, _) in cumulative_columns:
+            initial_state[column] = initial_state[key]
+    if options.deltas:
+        if set(options.deltas) - set(initial_state):
+            raise KeyError(f"Invalid state keys {sorted(set(options.deltas) - set(initial_state))} in deltas")
+        # The initial state has no previous timestep to compute a delta from
+        for key in options.deltas:
+            initial_state[f"{key}_delta"] = None
     initial_state["simulation"] = simulation
     initial_state["subset"] = subset
-    initial_state["run"] = run + 1
-    initial_state["substep"] = 0
+    initial_state["run"] = run_index
+    if options.run_uuid:
+        initial_state["run_uuid"] = generate_run_uuid(simulation, run_index, subset, options.seed)
+    if options.initial_history and (options.run_uuid or options.deltas or options.cumulative):
+        _fill_initial_history(
+            options.initial_history, initial_state, options.run_uuid, options.deltas, cumulative_columns if options.cumulative else []
+        )
+    if not 0 <= options.initial_substep < max(len(state_update_blocks), 1):
+        raise ValueError(f"Initial substep {options.initial_substep} must be less than the number of state update blocks")
+    initial_state["substep"] = options.initial_substep
+    # A PSU "label" is recorded as the substep_label of its substeps, when any PSU is labelled
+    substep_labels = any("label" in psu for psu in state_update_blocks)
+    if substep_labels:
+        initial_state["substep_label"] = None
+    # The iteration of each substep is recorded when any PSU records its iterations, distinguishing records of the same substep
+    substep_iterations = any(psu.get("record_iterations", False) for psu in state_update_blocks)
+    if substep_iterations:
+        initial_state["iteration"] = None
     if not initial_state.get("timestep", False):
-        initial_state["timestep"] = 0
+        initial_state["timestep"] = options.start_timestep
+    if options.record_wall_time:
+        wall_clock = WALL_CLOCKS["epoch" if options.record_wall_time is True else options.record_wall_time]
+        initial_state["wall_time"] = wall_clock()
+    if options.record_updated:
+        initial_state["__updated__"] = []
+    if options.run_metadata:
+        conflicting_keys = set(options.run_metadata) & set(initial_state)
+        if conflicting_keys:
+            raise ValueError(f"Run metadata keys {sorted(conflicting_keys)} conflict with State Variables")
+        initial_state.update(options.run_metadata)
 
+    if options.state_schema:
+        _check_types(initial_state, initial_state.keys(), options.state_schema)
     result.append([initial_state])
+    # States are yielded once recorded, as in the results, so only the final state is yielded when final_only is set
+    yielded = [] if options.final_only else [record for records in result for record in records]
+    yield from yielded
+    if step_timesteps:
+        yield TIMESTEP_END
+    # Index of the first timestep recorded by this run, following any initial history
+    first_recorded = len(result)
+    last_timestep = timesteps + (1 if options.initial_substep else 0) - 1
+    # Recorded states of the most recent timestep, replacing it once superseded as the previous state, when filtering records
+    passed = pending = None
+    filtered = options.record_when or options.record_stride
+    state_keys = set(initial_state.keys())
 
-    for timestep in range(0, timesteps):
-        previous_state: dict = (
-            result[0][0].copy()
-            if timestep == 0
-            else result[-1][-1:][0].copy()
-        )
+    if options.tracer:
+        # Trace attributes are shared with, and updated for, each traced function
+        trace_attributes = {"simulation": simulation, "run": run_index, "subset": subset}
+        state_update_blocks = _wrap_state_update_blocks(partial(_traced, options.tracer, trace_attributes), state_update_blocks)
+    profile = metadata.get("profile", None)
+    if profile is not None:
+        state_update_blocks = _wrap_state_update_blocks(partial(_profiled, profile), state_update_blocks)
+
+    block_params = [_psu_params(params, psu) for psu in state_update_blocks]
+    completed_substeps = 0
+    signal_keys = set()
+    # Signals each lazy State Update Function was last called with, per substep
+    lazy_signals = {}
+
+    # When resuming mid-timestep, the remaining substeps of the initial timestep are completed first
+    for timestep in range(0, timesteps + (1 if options.initial_substep else 0)):
+        if options.deadline and time.time() > options.deadline:
+            # The run is interrupted between timesteps, returning the completed timesteps
+            metadata["timed_out"] = True
+            metadata["terminated_at"] = result[-1][-1]["timestep"]
+            break
+        if options.max_memory_mb and timestep % MEMORY_CHECK_INTERVAL == 0:
+            # Raised before the timestep is executed, rather than the process being killed when out of memory
+            check_memory_budget(options.max_memory_mb, f"at timestep {result[-1][-1]['timestep']} of simulation {simulation} / run {run} / subset {subset}")
+        first_substep = options.initial_substep if timestep == 0 else 0
+        previous_state: dict = result[-1][-1].copy()
+        current_timestep = previous_state["timestep"] + (0 if first_substep else 1)
+        if options.tracer:
+            trace_attributes.update({"timestep": current_timestep, "substep": 0})
+            options.tracer("start", "timestep", dict(trace_attributes))
 
         substeps: list = []
         substate: dict = previous_state.copy()
+        stopped = False
+        # Signals of cacheable PSUs, invalidated each timestep
+        signal_cache = {}
+        # Substep that last updated each State Variable within the timestep
+        updated_by = {}
 
-        for (substep, psu) in enumerate(state_update_blocks):
+        for (substep, psu) in enumerate(state_update_blocks[first_substep:], first_substep):
             substate: dict = (
-                previous_state.copy() if substep == 0 else substeps[substep - 1].copy()
-            )
-            substate_copy = pickle.loads(pickle.dumps(substate, -1)) if deepcopy else substate.copy()
-            substate["substep"] = substep + 1
-            
-            signals: dict = reduce_signals(
-                params, substep, result, substate_copy, psu, deepcopy
+                previous_state.copy() if not substeps else substeps[-1].copy()
             )
+            for key in signal_keys:
+                # Recorded signals aren't State Variables, so aren't passed to the following substeps
+                substate.pop(key, None)
+            # A PSU with a "converged" predicate is re-executed until converged, or the maximum number of iterations
+            converged = psu.get("converged", None)
+            max_iterations = psu.get("max_iterations", DEFAULT_MAX_ITERATIONS) if converged else 1
+            psu_params = block_params[substep]
+            if options.seed is not None and options.seed_substeps:
+                # Each substep draws from a stream seeded independently of the draws of the preceding substeps
+                _seed_random(derive_substep_seed(metadata["seed"], current_timestep, substep + 1))
+            # Policy activation is drawn once per substep, shared by the reductions of each State Update Function and iteration
+            active_policies = _draw_active_policies(psu)
+            for iteration in range(1, max_iterations + 1):
+                substate_copy = _deepcopy_state(substate, options.fast_list_copy, options.serializer, options.deepcopy_keys) if deepcopy else substate.copy()
+                substate["substep"] = substep + 1
+                if substep_labels:
+                    substate["substep_label"] = psu.get("label", None)
+                if substep_iterations:
+                    substate["iteration"] = iteration
+                if options.tracer:
+                    trace_attributes["substep"] = substep + 1
 
-            updated_state = map(
-                partial(_update_state, initial_state, params, substep, result, substate_copy, signals),
-                psu["variables"].items()
-            )
-            substate.update(updated_state)
-            substate["timestep"] = (previous_state["timestep"] + 1) if timestep == 0 else timestep + 1
+                if psu.get("sequential_variables", False):
+                    # Policies are re-evaluated before each State Update Function, using the substate updated by the preceding functions
+                    updated_variables = []
+                    for (index, variable) in enumerate(psu["variables"].items()):
+                        variable_substate = substate_copy if index == 0 else (
+                            _deepcopy_state(substate, options.fast_list_copy, options.serializer, options.deepcopy_keys) if deepcopy else substate.copy()
+                        )
+                        signals: dict = _cached_reduce_signals(
+                            signal_cache, psu_params, substep, result, variable_substate, psu, deepcopy, options.check_policy_mutation, options.allow_stop, options.frozen_state, active_policies
+                        )
+                        if signals is STOP:
+                            stopped = True
+                            break
+                        if "expected_signals" in psu:
+                            _check_signals(signals, psu["expected_signals"], substep, current_timestep)
+                        if options.check_signal_finite:
+                            _check_signal_finite(signals, substep, current_timestep)
+                        if "lazy" in psu and not _changed_variables([variable], psu["lazy"], signals, lazy_signals, substep):
+                            continue
+                        variable_update = [
+                            _update_state(initial_state, psu_params, substep, result, variable_substate, signals, variable, options.allow_new_keys)
+                        ]
+                        if options.duplicate_updates != "last":
+                            variable_update = _resolve_duplicate_updates(
+                                substate, variable_update, updated_by, substep, current_timestep, options.duplicate_updates
+                            )
+                        substate.update(variable_update)
+                        updated_variables.append(variable)
+                        if options.validators:
+                            _validate_state(substate, [variable[0]], options.validators, current_timestep, substep)
+                else:
+                    signals: dict = _cached_reduce_signals(
+                        signal_cache, psu_params, substep, result, substate_copy, psu, deepcopy, options.check_policy_mutation, options.allow_stop, options.frozen_state, active_policies
+                    )
+                    stopped = signals is STOP
+                    if not stopped and "expected_signals" in psu:
+                        _check_signals(signals, psu["expected_signals"], substep, current_timestep)
+                    if not stopped and options.check_signal_finite:
+                        _check_signal_finite(signals, substep, current_timestep)
+                    if not stopped:
+                        # The State Update Functions called, excluding lazy functions skipped as their signals are unchanged
+                        updated_variables = (
+                            _changed_variables(psu["variables"].items(), psu["lazy"], signals, lazy_signals, substep)
+                            if "lazy" in psu else psu["variables"].items()
+                        )
+                        updated_state = map(
+                            partial(_update_state, initial_state, psu_params, substep, result, substate_copy, signals, allow_new_keys=options.allow_new_keys),
+                            updated_variables
+                        )
+                        if options.duplicate_updates != "last":
+                            updated_state = _resolve_duplicate_updates(
+                                substate, updated_state, updated_by, substep, current_timestep, options.duplicate_updates
+                            )
+                        substate.update(updated_state)
+                        if options.validators:
+                            _validate_state(substate, psu["variables"].keys(), options.validators, current_timestep, substep)
+                if stopped:
+                    # The stopped substep isn't recorded, and the remaining substeps of the timestep are skipped
+                    break
+                substate["timestep"] = current_timestep
+                if options.allow_new_keys and substate.keys() - state_keys:
+                    # State Variables added during the run are back-filled in the earlier records
+                    new_keys = substate.keys() - state_keys
+                    for record in [record for records in result for record in records] + substeps:
+                        for key in new_keys:
+                            record.setdefault(key, MISSING)
+                    state_keys |= new_keys
+                if options.record_updated:
+                    substate["__updated__"] = [key for (key, _) in updated_variables]
+                if options.check_finite:
+                    _check_finite(substate, psu["variables"].keys(), options.check_finite)
+                if options.state_schema:
+                    _check_types(substate, psu["variables"].keys(), options.state_schema)
+                if options.strict_schema:
+                    _check_schema(substate, state_keys)
+                if options.record_signals:
+                    recorded_signals = {f"{SIGNAL_PREFIX}{key}": value for (key, value) in signals.items()}
+                    # Only the recorded signals are removed before the following substep, so can't replace a State Variable
+                    conflicting_keys = recorded_signals.keys() & state_keys
+                    if conflicting_keys:
+                        raise ValueError(f"Recorded signals {sorted(conflicting_keys)} conflict with State Variables")
+                    substate.update(recorded_signals)
+                    signal_keys.update(recorded_signals)
+
+                if not converged or converged(substate_copy, substate):
+                    break
+                elif iteration == max_iterations:
+                    logging.warning(
+                        f"Substep {substep + 1} of timestep {substate['timestep']} didn't converge after {max_iterations} iterations"
+                    )
+                elif psu.get("record_iterations", False):
+                    substeps.append(substate)
+                    substate = substate.copy()
+            if stopped:
+                break
+            for key in options.deltas or []:
+                # The change from the final state of the previous timestep
+                substate[f"{key}_delta"] = substate[key] - previous_state[key]
             substeps.append(substate)
+            completed_substeps += 1
+            if options.gc_interval and completed_substeps % options.gc_interval == 0:
+                # Only the younger generations are collected, freeing reference cycles created by recent substeps
+                # without traversing the states retained as results
+                gc.collect(1)
+            if options.after_substep:
+                try:
+                    options.after_substep(state=substate)
+                except Exception as error:
+                    raise SubstepHookError(
+                        simulation, run_index, subset, substate["timestep"], substate["substep"]
+                    ) from error
 
+        if stopped and not substeps:
+            # The previous state is carried forward when stopped before any substep completed
+            substeps = [{**previous_state, "timestep": current_timestep, "substep": 0}]
+            if substep_labels:
+                substeps[0]["substep_label"] = None
+            if substep_iterations:
+                substeps[0]["iteration"] = None
         substeps = [substate] if not substeps else substeps
+        for (key, column, aggregate) in cumulative_columns if options.cumulative else []:
+            # Aggregated once per timestep, with the final substate and the aggregate of the previous timestep,
+            # so that the aggregate doesn't depend on the number of substeps, or on drop_substeps
+            substeps[-1][column] = aggregate(previous_state[column], substeps[-1][key])
+        if options.record_wall_time:
+            wall_time = wall_clock()
+            for substate in substeps:
+                substate["wall_time"] = wall_time
+        if options.substeps_per_timestep:
+            # Substeps are truncated to the final substeps, or padded with copies of the final substate, numbered consecutively
+            substeps = substeps[-options.substeps_per_timestep:] + [
+                {**substeps[-1], "substep": substeps[-1]["substep"] + index}
+                for index in range(1, options.substeps_per_timestep - len(substeps) + 1)
+            ]
         result.append(substeps if not drop_substeps else [substeps.pop()])
+        if filtered:
+            # States of the timestep passing record_when, decided once the timestep completes
+            passed = [substate for substate in result[-1] if options.record_when(state=substate)] if options.record_when else result[-1]
+            if pending is not None:
+                # The previous timestep is retained as the previous state, and replaced by its recorded states once superseded,
+                # so the state history seen by Policy and State Update Functions only contains the recorded timesteps
+                if pending:
+                    result[-2] = pending
+                else:
+                    del result[-2]
+            pending = [] if options.record_stride and current_timestep % options.record_stride else passed
+        if options.final_only:
+            # Only the most recent timestep is retained as state history, including the history seen by Policy and State Update Functions
+            del result[:-1]
+        if options.tracer:
+            trace_attributes["substep"] = 0
+            options.tracer("end", "timestep", dict(trace_attributes))
+        terminated = bool(options.terminating_condition and options.terminating_condition(state=result[-1][-1]))
+        if terminated or timestep == last_timestep:
+            yielded = _final_records(result[-1], passed, options.final_only)
+        else:
+            yielded = [] if options.final_only else (pending if filtered else result[-1])
+        yield from yielded
+        if terminated:
+            # The run ends before its final timestep, recording the last timestep executed
+            metadata["terminated_at"] = current_timestep
+            break
+        if step_timesteps:
+            yield TIMESTEP_END
+
+    if filtered and len(result) - 1 >= first_recorded:
+        # The final timestep is recorded regardless of the stride, and its final state regardless of record_when
+        result[-1] = _final_records(result[-1], passed, False)
+    if options.final_only:
+        result[:] = [[result[-1][-1]]]
+    # States of a run interrupted between timesteps, e.g. by a timeout, are recorded once the run ends
+    yield from [
+        record for record in result[-1] if not any(record is yielded_record for yielded_record in yielded)
+    ]
+    return result
+
+
+def _policies_only_run(
+    result: list,
+    simulation: int,
+    timesteps: int,
+    run_index: int,
+    subset: int,
+    initial_state: dict,
+    state_update_blocks: list,
+    params: dict,
+    deepcopy: bool,
+    check_policy_mutation: bool,
+):
+    # Policy Functions are evaluated against the unchanged initial state, recording the reduced signals of each substep
+    state = {
+        **initial_state,
+        "simulation": simulation,
+        "subset": subset,
+        "run": run_index,
+        "substep": 0,
+        "timestep": initial_state.get("timestep") or 0,
+    }
+    state_history = [[state]]
+    for timestep in range(1, timesteps + 1):
+        substeps = []
+        for (substep, psu) in enumerate(state_update_blocks):
+            substate = {**state, "timestep": state["timestep"] + timestep, "substep": substep + 1}
+            signals = _reduce_signals(
+                _psu_params(params, psu), substep, state_history, substate, psu, deepcopy, check_policy_mutation, active_policies=_draw_active_policies(psu)
+            )
+            substeps.append({
+                "simulation": simulation,
+                "subset": subset,
+                "run": run_index,
+                "timestep": substate["timestep"],
+                "substep": substate["substep"],
+                "signals": signals,
+            })
+        result.append(substeps)
     return result
 
 
-def single_run(
+def _capture_stdout(stdout):
+    return contextlib.redirect_stdout(stdout) if stdout else contextlib.nullcontext()
+
+
+def iter_single_run(
     simulation=0,
     timesteps=1,
     run=0,
@@ -91,12 +907,60 @@ def single_run(
     params={},
     deepcopy: bool=True,
     drop_substeps: bool=False,
-) -> Tuple[list, Exception, str]:
+    options: RunOptions=None,
+    step_timesteps: bool=False,
+) -> Generator[dict, None, Tuple[list, Exception, str, dict]]:
+    """
+    Executes a single run, yielding the recorded states of each timestep as it completes,
+    and returning the results along with any error, traceback, and run metadata.
+    The Engine and Simulation options of the run are passed as `options`, defaulting to `RunOptions()`.
+    When `step_timesteps` is set, `TIMESTEP_END` is also yielded after each timestep.
+    """
+    options = options if options is not None else RunOptions()
     result = []
+    # Additional run metadata, returned along with the results
+    metadata = {}
+    if options.pin_cores:
+        _pin_to_core()
+    if options.profile:
+        metadata["profile"] = {}
+    stdout = io.StringIO() if options.capture_stdout else None
+    started = time.perf_counter() if options.on_run_finished else None
+    if options.seed is not None:
+        # The seed derived for, and applied to, the run, to replay the run in isolation
+        metadata["seed"] = derive_run_seed(options.seed, simulation, run, subset)
+        _seed_random(metadata["seed"])
+
+    state_update_blocks = list(map(_normalize_psu, state_update_blocks))
 
     try:
-        return (
-            _single_run(
+        with contextlib.ExitStack() as stack:
+            if options.manage_lifecycle:
+                _enter_lifecycle_objects(stack, state_update_blocks)
+            if options.scratch:
+                # A scratch dict is created per run, shared by the Policy and State Update Functions of the run, and not recorded
+                state_update_blocks = _wrap_state_update_blocks(partial(_with_scratch, {}), state_update_blocks)
+            if options.policies_only:
+                with _capture_stdout(stdout):
+                    policies_only_result = _policies_only_run(
+                        result,
+                        simulation,
+                        timesteps,
+                        run if options.one_based_run is False else run + 1,
+                        subset,
+                        initial_state,
+                        state_update_blocks,
+                        params,
+                        deepcopy,
+                        options.check_policy_mutation,
+                    )
+                return (
+                    policies_only_result,
+                    None, # Error
+                    None, # Traceback
+                    metadata,
+                )
+            states = _single_run(
                 result,
                 simulation,
                 timesteps,
@@ -107,27 +971,69 @@ def single_run(
                 params,
                 deepcopy,
                 drop_substeps,
-            ),
-            None, # Error
-            None, # Traceback
-        )
+                options,
+                metadata,
+                step_timesteps,
+            )
+            while True:
+                # Standard output is captured while the run executes, rather than while the consumer handles each state
+                with _capture_stdout(stdout):
+                    try:
+                        state = next(states)
+                    except StopIteration as stop:
+                        return (
+                            stop.value,
+                            None, # Error
+                            None, # Traceback
+                            metadata,
+                        )
+                yield state
     except Exception as error:
         trace = traceback.format_exc()
         print(trace)
         logging.warning(
             f"Simulation {simulation} / run {run} / subset {subset} failed! Returning partial results if Engine.raise_exceptions == False."
         )
-        return (result, error, trace)
+        return (result, error, trace, metadata)
+    finally:
+        if stdout:
+            metadata["stdout"] = stdout.getvalue()
+        if options.on_run_finished:
+            # The hook is called whether or not the run failed, so an error raised by the hook is logged,
+            # rather than masking the results, or error, of the run
+            try:
+                # The run index passed to hooks is zero-based unless `one_based_run` is enabled
+                options.on_run_finished(
+                    simulation=simulation, run=run + 1 if options.one_based_run else run, subset=subset, seconds=time.perf_counter() - started
+                )
+            except Exception:
+                logging.warning(
+                    f"Simulation {simulation} / run {run} / subset {subset} on_run_finished hook failed!\n{traceback.format_exc()}"
+                )
+
+
+def single_run(*args, **kwargs) -> Tuple[list, Exception, str, dict]:
+    """
+    Executes a single run, returning the results along with any error, traceback, and run metadata.
+    Accepts the same arguments as `iter_single_run()`.
+    """
+    stream = iter_single_run(*args, **kwargs)
+    while True:
+        try:
+            next(stream)
+        except StopIteration as stop:
+            return stop.value
 
 
 def _single_run_wrapper(args):
     run_args, raise_exceptions = args
     try:
-        results, exception, traceback = single_run(*tuple(run_args))
+        results, exception, traceback, metadata = single_run(*tuple(run_args))
         if raise_exceptions and exception:
             raise exception
         else:
             return results, {
+                    **metadata,
                     'exception': exception,
                     'traceback': traceback,
                     'simulation': run_args.simulation,
@@ -144,27 +1050,95 @@ def _single_run_wrapper(args):
             return [], e
 
 
-def generate_parameter_sweep(params: Dict[str, List[any]]):
+class Scenarios(dict):
+    """
+    A parameter value mapping scenario names to their parameter value, swept like a list of the parameter values,
+    with the name of each scenario added to the parameter subset as `{key}_scenario`.
+    """
+
+
+def _expand_scenarios(params: dict) -> dict:
+    if not any(isinstance(value, Scenarios) for value in params.values()):
+        return params
+    expanded = {}
+    for (key, value) in params.items():
+        if isinstance(value, Scenarios):
+            if not value:
+                raise ValueError(f"Parameter {key} has no scenarios")
+            expanded[key] = list(value.values())
+            expanded[f"{key}_scenario"] = list(value.keys())
+        else:
+            expanded[key] = value
+    return expanded
+
+
+def _is_swept(value) -> bool:
+    # Strings and dicts are sequence-like, but treated as single parameter values
+    return hasattr(value, "__len__") and hasattr(value, "__getitem__") and not isinstance(value, (str, bytes, dict))
+
+
+def _check_param(key, value):
+    # Iterators (e.g. generators) and unordered collections can't be indexed per parameter subset
+    if isinstance(value, (Iterator, Set, MappingView)):
+        raise TypeError(
+            f"Parameter {key} of type {type(value).__name__} can't be indexed per parameter subset, "
+            f"use a list of parameter values instead"
+        )
+    if _is_swept(value) and len(value) == 0:
+        raise ValueError(f"Parameter {key} of type {type(value).__name__} has no values")
+
+
+def generate_parameter_sweep(params: Dict[str, List[any]], sweep: bool=True, dedup_sweep: bool=False):
     param_sweep = []
-    max_len = 0
+    max_len = 1
+    for (key, value) in params.items():
+        _check_param(key, value)
+    params = _expand_scenarios(params)
+
+    if not sweep:
+        # A single constant parameter subset, where single value lists are unwrapped
+        return [{key: value[0] if _is_swept(value) and len(value) == 1 else value for (key, value) in params.items()}]
+
     for value in params.values():
-        if len(value) > max_len:
+        if _is_swept(value) and len(value) > max_len:
             max_len = len(value)
 
+    for (key, value) in params.items():
+        # Single value lists are intentionally used for every parameter subset
+        if _is_swept(value) and 1 < len(value) < max_len:
+            warnings.warn(
+                f"Parameter {key} has {len(value)} values, less than the {max_len} parameter subsets, "
+                f"and will be padded with its last value"
+            )
+
     for sweep_index in range(0, max_len):
         param_set = {}
         for (key, value) in params.items():
-            param = (
-                value[sweep_index]
-                if sweep_index < len(value)
-                else value[-1]
-            )
+            if not _is_swept(value):
+                # Single parameter values are used for every parameter subset
+                param = value
+            else:
+                param = (
+                    value[sweep_index]
+                    if sweep_index < len(value)
+                    else value[-1]
+                )
             param_set[key] = param
-        param_sweep.append(param_set)
+        # Parameter subsets are compared by value, as parameter values aren't necessarily hashable
+        if not dedup_sweep or param_set not in param_sweep:
+            param_sweep.append(param_set)
 
     return param_sweep
 
 
+def select_run_params(run_params: Dict[str, List[any]], run: int):
+    # As with parameter sweeps, runs beyond the length of a parameter list use its last value
+    return {
+        key: value[run] if run < len(value) else value[-1]
+        for (key, value) in run_params.items()
+    }
+
+
 def _add_signals(acc, a: Dict[str, any]):
     for (key, value) in a.items():
         if acc.get(key, None):
@@ -174,10 +1148,89 @@ def _add_signals(acc, a: Dict[str, any]):
     return acc
 
 
-def reduce_signals(params: dict, substep: int, result: list, substate: dict, psu: dict, deepcopy: bool=True):
-    policy_results: List[Dict[str, any]] = list(
-        map(lambda function: function(params, substep, result, substate), psu["policies"].values())
-    )
+_NO_ACCUMULATOR_INIT = object()
+
+
+def _reduce_signals_with(reducer, params: dict, substep: int, acc, a: Dict[str, any], accumulator_init=_NO_ACCUMULATOR_INIT):
+    for (key, value) in a.items():
+        if key in acc:
+            acc[key] = reducer(params, substep, acc[key], value)
+        elif accumulator_init is not _NO_ACCUMULATOR_INIT:
+            acc[key] = reducer(params, substep, copy.deepcopy(accumulator_init), value)
+        else:
+            acc[key] = value
+    return acc
+
+
+def _deep_merge(params: dict, substep: int, acc, value):
+    # Dict signals are merged recursively, numeric leaves are summed, and other leaves are overridden by later policies
+    if isinstance(acc, dict) and isinstance(value, dict):
+        merged = dict(acc)
+        for (key, item) in value.items():
+            merged[key] = _deep_merge(params, substep, merged[key], item) if key in merged else item
+        return merged
+    elif isinstance(acc, numbers.Number) and isinstance(value, numbers.Number):
+        return acc + value
+    return value
+
+
+# Built-in reducers, selected using the name of the PSU "reducer"
+REDUCERS = {"deep_merge": _deep_merge}
+
+
+def _call_policy_checking_mutation(params: dict, substep: int, result: list, substate: dict, frozen_state: bool, policy: Tuple[str, any]):
+    name, function = policy
+    snapshot = pickle.dumps(substate, -1)
+    policy_result = function(params, substep, result, MappingProxyType(substate) if frozen_state else substate)
+    if pickle.dumps(substate, -1) != snapshot:
+        raise RuntimeError(f"Policy function {name} mutated the state passed to it at substep {substep + 1}")
+    return policy_result
+
+
+def _draw_active_policies(psu: dict):
+    # A PSU "activation" maps Policy Functions to the probability they're called each substep, drawn from the seeded random module
+    activation = psu.get("activation", None)
+    if not activation:
+        return None
+    policies = psu["policies"]
+    if activation.keys() - policies.keys():
+        raise KeyError(f"Activation of unknown policies {sorted(activation.keys() - policies.keys())} in partial state update block")
+    return {name for name in policies if name not in activation or random.random() < activation[name]}
+
+
+def reduce_signals(params: dict, substep: int, result: list, substate: dict, psu: dict, deepcopy: bool=True, check_mutation: bool=False, allow_stop: bool=False, frozen_state: bool=False, active_policies: set=None):
+    psu = _normalize_psu(psu)
+    if active_policies is None:
+        active_policies = _draw_active_policies(psu)
+    return _reduce_signals(params, substep, result, substate, psu, deepcopy, check_mutation, allow_stop, frozen_state, active_policies)
+
+
+def _reduce_signals(params: dict, substep: int, result: list, substate: dict, psu: dict, deepcopy: bool=True, check_mutation: bool=False, allow_stop: bool=False, frozen_state: bool=False, active_policies: set=None):
+    # The PSU is already normalized, as the state update blocks are normalized once per run rather than every substep
+    policies = psu["policies"]
+    # The active policies are drawn once per substep by the caller, so that every reduction of the substep calls the same policies
+    if active_policies is not None:
+        policies = {name: function for (name, function) in policies.items() if name in active_policies}
+    if check_mutation:
+        policy_results: List[Dict[str, any]] = list(
+            map(partial(_call_policy_checking_mutation, params, substep, result, substate, frozen_state), policies.items())
+        )
+    else:
+        # Policy Functions receive a read-only view of the state when frozen
+        policy_substate = MappingProxyType(substate) if frozen_state else substate
+        policy_results: List[Dict[str, any]] = list(
+            map(lambda function: function(params, substep, result, policy_substate), policies.values())
+        )
+
+    # A Policy Function may return a list of signals, each reduced as if returned by a separate Policy Function
+    policy_results = [
+        signals
+        for policy_result in policy_results
+        for signals in (policy_result if isinstance(policy_result, list) else [policy_result])
+    ]
+
+    if allow_stop and any(policy_result is STOP for policy_result in policy_results):
+        return STOP
 
     result: dict = {}
     result_length = len(policy_results)
@@ -186,4 +1239,18 @@ def reduce_signals(params: dict, substep: int, result: list, substate: dict, psu
     elif result_length == 1:
         return pickle.loads(pickle.dumps(policy_results[0], -1)) if deepcopy else policy_results[0].copy()
     else:
+        # A PSU "reducer" with signature (params, substep, accumulator, value) overrides the default addition of signals
+        reducer = psu.get("reducer", None)
+        if isinstance(reducer, str):
+            if reducer not in REDUCERS:
+                raise ValueError(f"Reducer must be a function or one of {list(REDUCERS)}, not {reducer}")
+            reducer = REDUCERS[reducer]
+        if reducer:
+            # A PSU "accumulator_init" seeds the reduction of each signal key, rather than its first value
+            accumulator_init = psu.get("accumulator_init", _NO_ACCUMULATOR_INIT)
+            return reduce(
+                lambda acc, a: _reduce_signals_with(reducer, params, substep, acc, a, accumulator_init),
+                policy_results,
+                result,
+            )
         return reduce(_add_signals, policy_results, result)
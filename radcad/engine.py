@@ -1,14 +1,47 @@
 import radcad.core as core
 import radcad.wrappers as wrappers
 from radcad.backends import Backend
-from radcad.utils import flatten, extract_exceptions
+from radcad.utils import flatten, extract_exceptions, reconcile_schema, write_ndjson, write_parquet
 
 import multiprocessing
 import copy
+import logging
+import numbers
+import os
+import random
+import threading
+import time
+import traceback
 
 
 # Get machine CPU count
 cpu_count = multiprocessing.cpu_count() - 1 or 1
+RUN_ORDERS = ["sequential", "reverse", "shuffle"]
+
+# Module-level hooks called around every run() of an Experiment or Simulation, e.g. for telemetry
+_pre_run_hook = None
+_post_run_hook = None
+_run_hooks_lock = threading.Lock()
+
+
+def set_pre_run_hook(hook=None):
+    """
+    Sets a hook called with `executable=` the Experiment or Simulation before every `run()`, across all Engines,
+    e.g. for a wrapping framework to observe all simulations. Pass `None` to remove the hook.
+    """
+    global _pre_run_hook
+    with _run_hooks_lock:
+        _pre_run_hook = hook
+
+
+def set_post_run_hook(hook=None):
+    """
+    Sets a hook called with `executable=` the Experiment or Simulation after every `run()`, across all Engines,
+    including runs that raise an exception. An exception raised by the hook is logged as a warning. Pass `None` to remove the hook.
+    """
+    global _post_run_hook
+    with _run_hooks_lock:
+        _post_run_hook = hook
 
 class Engine:
     def __init__(self, **kwargs):
@@ -17,10 +50,43 @@ class Engine:
 
         Args:
             **backend (Backend): Which execution backend to use (e.g. Pathos, Multiprocessing, etc.). Defaults to `Backend.DEFAULT` / `Backend.PATHOS`.
-            **processes (int, optional): Number of system CPU processes to spawn. Defaults to `multiprocessing.cpu_count() - 1 or 1`
+            **processes (int, optional): Number of system CPU processes, or threads when using `Backend.THREADS`, to spawn. Defaults to `multiprocessing.cpu_count() - 1 or 1`
             **raise_exceptions (bool): Whether to raise exceptions, or catch them and return exceptions along with partial results. Default to `True`.
             **deepcopy (bool): Whether to enable deepcopy of State Variables, alternatively leaves safety up to user with improved performance. Defaults to `True`.
             **drop_substeps (bool): Whether to drop simulation result substeps during runtime to save memory and improve performance. Defaults to `False`.
+            **final_only (bool): Whether to only return the final state of each run, discarding state history during runtime, so the state history passed to Policy and State Update Functions only contains the previous timestep. Defaults to `False`.
+            **record_stride (int): Only record every Nth timestep, along with the initial state and final timestep, while still executing every timestep. The state history passed to Policy and State Update Functions is truncated to the recorded timesteps. Defaults to `None`, recording every timestep.
+            **record_updated (bool): Whether to record the State Variables updated in each substep, as a list of keys in an `__updated__` key of each state. Defaults to `False`.
+            **one_based_run (bool): Whether the `run` index recorded in the state, used to derive `run_uuid`, and passed to hooks as `Context.run`, `run_metadata`, and `on_run_finished`, is one-based (`True`), or zero-based (`False`). Defaults to `None`, recording a one-based index in the state, and passing a zero-based index to hooks.
+            **fast_list_copy (bool): Whether to copy State Variables that are lists of dicts with immutable values (e.g. agents) without serialization, when `deepcopy` is enabled. Defaults to `False`.
+            **run_uuid (bool): Whether to inject a deterministic `run_uuid`, derived from the simulation, run, and subset indices, and the seed when set, into the state of each run. Defaults to `False`.
+            **check_finite (bool | str): Whether to check that numeric State Variables are finite after each update, either raising a `ValueError` (`True` or `"raise"`) or logging a warning (`"warn"`). Defaults to `False`.
+            **tracer (Callable): Called with `(event, span, attributes)` on the `"start"` and `"end"` event of each `"timestep"`, `"policy"`, and `"variable"` span, or the `"error"` event of a function that raised an exception, where attributes contain the simulation, run, subset, timestep, substep, and function name. Defaults to `None`.
+            **check_policy_mutation (bool): Whether to check that Policy Functions don't mutate the state passed to them, at the cost of serializing the state before and after each Policy Function. Defaults to `False`.
+            **spill_threshold (int): Maximum number of states to buffer in memory, spilling older states to a temporary file and returning a lazy `SpilledResults` sequence. With the `SINGLE_PROCESS` backend states are spilled as each run completes, while other backends collect the results of all runs from the worker processes before spilling. Defaults to `None`, returning a list.
+            **compression (str): Codec used to compress states spilled to disk, one of `"gzip"`, `"bz2"`, `"lzma"`, or `"zstd"` (requires the `zstandard` package). Defaults to `None`, no compression.
+            **profile (bool): Whether to time each Policy and State Update Function, with the total calls and seconds per function of all runs available as `profile` on the Experiment or Simulation, and per run in `exceptions`. Defaults to `False`.
+            **strict_schema (bool): Whether to check that the state contains exactly the initial State Variables after each substep, raising a `KeyError` with any added or removed keys. Defaults to `False`.
+            **allow_stop (bool): Whether Policy Functions can return `radcad.core.STOP` to stop the remaining substeps of the timestep, carrying the most recent substate forward. Defaults to `False`.
+            **params_immutable (bool): Whether parameters are never mutated, so that the same parameter subset object can be passed to every run without a defensive deepcopy per run. Unsafe if Policy or State Update Functions mutate parameters. Defaults to `False`.
+            **capture_stdout (bool): Whether to capture the standard output of each run, e.g. printed from Policy and State Update Functions, as `stdout` in the run's `exceptions` entry. Not supported with `Backend.THREADS`. Defaults to `False`.
+            **run_order (str): Order to execute runs in, one of `"sequential"`, `"reverse"`, or `"shuffle"`, e.g. to expose coupling between runs or balance the cost of runs across processes. Results are returned in sequential order regardless. Defaults to `"sequential"`.
+            **run_order_seed (int): Random seed used to shuffle runs when `run_order` is `"shuffle"`. Defaults to `None`.
+            **manage_lifecycle (bool): Whether to acquire and release the objects that Policy and State Update Functions are bound methods of around each run, calling `__enter__()` and `__exit__()`, or `setup()` and `teardown()`, when defined. Defaults to `False`.
+            **melt (list): State Variables to melt into long-format results, with a row per state and melted State Variable, containing the remaining columns of the state, the name of the State Variable as `variable`, and its numeric value as `value`, e.g. for plotting libraries. Defaults to `None`, returning a row per state.
+            **seed_substeps (bool): Whether to also seed the `random` module, and `numpy.random` when installed, before each substep, with a seed derived from the run's seed, timestep, and substep, so that the random draws of each substep don't depend on the draws of the preceding substeps. Requires `seed`, and not supported with `Backend.THREADS`. Defaults to `False`.
+            **check_signal_finite (bool): Whether to check that numeric policy signals are finite after they're reduced, raising a `ValueError` naming the signal and substep, to catch non-finite values at the Policy Function producing them. Defaults to `False`.
+            **sort_columns (bool): Whether to sort the columns of each state in the results alphabetically, rather than in the order of the initial state followed by the bookkeeping keys. Defaults to `False`.
+            **duplicate_updates (str): How to handle a State Variable updated by more than one PSU within a timestep, one of `"last"`, keeping the last update, `"warn"`, keeping the last update with a warning, `"error"`, raising a `ValueError`, or `"sum"`, adding each repeated update to the value updated earlier in the timestep. Defaults to `"last"`.
+            **scratch (bool): Whether to pass a mutable `scratch` dict, created per run and not recorded in the results, as an additional last argument to each Policy and State Update Function, e.g. for counters and caches persisted across substeps. Defaults to `False`.
+            **pin_cores (bool): Whether to pin each worker process of a multi-process backend to a single CPU core, e.g. to reduce the variance of benchmarks. Only supported on Linux. Defaults to `False`.
+            **record_signals (bool): Whether to record the reduced policy signals of each substep in the state, prefixed with `signal_`, e.g. `signal_delta`. Recorded signals aren't passed to the following substeps. Defaults to `False`.
+            **frozen_state (bool): Whether Policy Functions receive the state as a read-only `types.MappingProxyType` view, rather than a dict, so that assigning State Variables raises a `TypeError`. Nested values remain mutable. Defaults to `False`.
+            **gc_interval (int): Number of substeps after which to collect the younger garbage collector generations within each run, bounding the peak memory of reference cycles created by substep-heavy models. Defaults to `None`, using the default garbage collection thresholds.
+            **seed (int): Random seed from which a seed is derived for each run, from the simulation, run, and subset indices, and applied to the `random` module, and `numpy.random` when installed, before the run. The derived seed is returned as `seed` in the run's `exceptions` entry. Not supported with `Backend.THREADS`. Defaults to `None`.
+            **allow_new_keys (bool): Whether State Update Functions can add State Variables that aren't in the initial state, back-filling the earlier records of the run with `radcad.core.MISSING`. Defaults to `False`, raising a `KeyError`.
+            **policies_only (bool): Whether to only evaluate Policy Functions, against the unchanged initial state, without executing State Update Functions. Each result is a record of the reduced `signals` of a substep, rather than a state. Defaults to `False`.
+            **debug_mode (bool): Whether to execute runs in the calling process regardless of the backend, so that breakpoints and debuggers such as `pdb` work inside Policy and State Update Functions, with the same results as the selected backend. Defaults to `False`.
             **_run_generator (tuple_iterator): Generator to generate simulation runs, used to implement custom execution backends. Defaults to  `iter(())`.
         """
         self.executable = None
@@ -29,41 +95,141 @@ class Engine:
         self.raise_exceptions = kwargs.pop("raise_exceptions", True)
         self.deepcopy = kwargs.pop("deepcopy", True)
         self.drop_substeps = kwargs.pop("drop_substeps", False)
+        self.final_only = kwargs.pop("final_only", False)
+        self.record_stride = kwargs.pop("record_stride", None)
+        self.record_updated = kwargs.pop("record_updated", False)
+        self.one_based_run = kwargs.pop("one_based_run", None)
+        self.fast_list_copy = kwargs.pop("fast_list_copy", False)
+        self.run_uuid = kwargs.pop("run_uuid", False)
+        self.check_finite = kwargs.pop("check_finite", False)
+        self.tracer = kwargs.pop("tracer", None)
+        self.check_policy_mutation = kwargs.pop("check_policy_mutation", False)
+        self.spill_threshold = kwargs.pop("spill_threshold", None)
+        self.compression = kwargs.pop("compression", None)
+        self.profile = kwargs.pop("profile", False)
+        self.strict_schema = kwargs.pop("strict_schema", False)
+        self.allow_stop = kwargs.pop("allow_stop", False)
+        self.params_immutable = kwargs.pop("params_immutable", False)
+        self.capture_stdout = kwargs.pop("capture_stdout", False)
+        self.debug_mode = kwargs.pop("debug_mode", False)
+        self.policies_only = kwargs.pop("policies_only", False)
+        self.allow_new_keys = kwargs.pop("allow_new_keys", False)
+        self.seed = kwargs.pop("seed", None)
+        self.gc_interval = kwargs.pop("gc_interval", None)
+        self.frozen_state = kwargs.pop("frozen_state", False)
+        self.record_signals = kwargs.pop("record_signals", False)
+        self.pin_cores = kwargs.pop("pin_cores", False)
+        self.scratch = kwargs.pop("scratch", False)
+        self.duplicate_updates = kwargs.pop("duplicate_updates", "last")
+        self.sort_columns = kwargs.pop("sort_columns", False)
+        self.check_signal_finite = kwargs.pop("check_signal_finite", False)
+        self.seed_substeps = kwargs.pop("seed_substeps", False)
+        self.melt = kwargs.pop("melt", None)
+        self.manage_lifecycle = kwargs.pop("manage_lifecycle", False)
+        self.run_order = kwargs.pop("run_order", "sequential")
+        self.run_order_seed = kwargs.pop("run_order_seed", None)
         self._run_generator = iter(())
+        self._skipped_runs = False
+        self._lazy_results = False
 
         if kwargs:
             raise Exception(f"Invalid Engine option in {kwargs}")
+        if self.pin_cores and not hasattr(os, "sched_setaffinity"):
+            raise ValueError("Pinning worker processes to CPU cores is only supported on Linux")
+        if self.gc_interval is not None and (not isinstance(self.gc_interval, int) or self.gc_interval < 1):
+            raise ValueError(f"Garbage collection interval must be a positive number of substeps, not {self.gc_interval}")
+        if self.run_order not in RUN_ORDERS:
+            raise ValueError(f"Run order must be one of {RUN_ORDERS}, not {self.run_order}")
+        if self.seed_substeps and self.seed is None:
+            raise ValueError("Seeding substeps requires the seed option")
+        self._check_backend_options()
+        if self.duplicate_updates not in core.DUPLICATE_UPDATES:
+            raise ValueError(f"Duplicate updates must be one of {core.DUPLICATE_UPDATES}, not {self.duplicate_updates}")
+        if self.melt is not None and (isinstance(self.melt, str) or not self.melt):
+            raise ValueError(f"Melted State Variables must be a non-empty list of keys, not {self.melt!r}")
 
-    def _run(self, executable=None, **kwargs):
+    def _check_backend_options(self):
+        if self.backend == Backend.THREADS and self.capture_stdout:
+            # Standard output is redirected for the whole process, so would be captured from every concurrent run
+            raise ValueError("Capturing standard output isn't supported with the THREADS backend, as threads share the standard output")
+        if self.backend == Backend.THREADS and self.seed_substeps:
+            # Reseeding the global random number generators before each substep would interleave the substeps of concurrent runs
+            raise ValueError("Seeding substeps isn't supported with the THREADS backend, as threads share the global random number generators")
+        if self.backend == Backend.THREADS and self.seed is not None:
+            # Threads share the global random number generators, which are reseeded at the start of each run
+            raise ValueError("Seeding runs isn't supported with the THREADS backend, as threads share the global random number generators")
+
+    def _run(self, executable=None, only=None, parquet_path=None, timeout_seconds=None, plan_only=False, ndjson_path=None, max_memory_mb=None, **kwargs):
         if not executable:
             raise Exception("Experiment or simulation required as Executable argument")
         self.executable = executable
 
         if kwargs:
             raise Exception(f"Invalid Engine option in {kwargs}")
+        if ndjson_path and parquet_path:
+            raise ValueError("Results can't be written to both a newline-delimited JSON and Parquet file")
+        if max_memory_mb is not None and (isinstance(max_memory_mb, bool) or not isinstance(max_memory_mb, numbers.Real) or max_memory_mb <= 0):
+            raise ValueError(f"Maximum memory must be a positive number of MB, not {max_memory_mb!r}")
 
         simulations = executable.simulations if isinstance(executable, wrappers.Experiment) else [executable]
         if not isinstance(self.backend, Backend):
             raise Exception(f"Execution backend must be one of {Backend.list()}")
-        configs = [
-            (
-                sim.model.initial_state,
-                sim.model.state_update_blocks,
-                sim.model.params,
-                sim.timesteps,
-                sim.runs,
-            )
-            for sim in simulations
-        ]
+        # The backend may be changed after the Engine is created, e.g. by the Experiment backend option
+        self._check_backend_options()
+        if plan_only:
+            return self._plan(simulations, only)
+
+        with _run_hooks_lock:
+            pre_run_hook, post_run_hook = _pre_run_hook, _post_run_hook
+        if pre_run_hook:
+            pre_run_hook(executable=executable)
+        try:
+            return self._execute(executable, simulations, only, parquet_path, timeout_seconds, ndjson_path, max_memory_mb)
+        finally:
+            # Called whether or not the run raised, e.g. to record the duration of failed runs
+            if post_run_hook:
+                # An error raised by the hook is logged, rather than masking the results, or exception, of the run
+                try:
+                    post_run_hook(executable=executable)
+                except Exception:
+                    logging.warning(f"Post-run hook failed!\n{traceback.format_exc()}")
+
+    def _execute(self, executable, simulations, only, parquet_path, timeout_seconds, ndjson_path, max_memory_mb):
+        for simulation in simulations:
+            if self.backend == Backend.THREADS and any("seed" in row for row in simulation.param_matrix or []):
+                raise ValueError("Seeding runs isn't supported with the THREADS backend, as threads share the global random number generators")
+            if self.melt and set(self.melt) - set(simulation.model.initial_state):
+                raise KeyError(f"Invalid state keys {sorted(set(self.melt) - set(simulation.model.initial_state))} in melt")
+        configs = [Engine._get_config(sim) for sim in simulations]
 
         result = []
 
         self.executable._before_experiment(experiment=(executable if isinstance(executable, wrappers.Experiment) else None))
 
-        self._run_generator = self._run_stream(configs)
+        # Units of execution to restrict the runs to, as (run, subset) or (simulation, run, subset) tuples
+        only = None if only is None else set(map(tuple, only))
+        # Wall-clock deadline, compared across processes, after which no further runs are started
+        deadline = time.time() + timeout_seconds if timeout_seconds is not None else None
+        self._skipped_runs = False
+        # Results are collected one run at a time, when supported by the backend, only when spilling, streaming, or bounding memory
+        self._lazy_results = bool(self.spill_threshold or ndjson_path or max_memory_mb)
+        self._run_generator = self._run_stream(self.executable, configs, only, deadline, max_memory_mb)
+        run_order = None
+        if self.run_order != "sequential":
+            # Runs are generated in sequential order, and reordered before execution
+            run_args = list(self._run_generator)
+            run_order = list(range(len(run_args)))
+            if self.run_order == "reverse":
+                run_order.reverse()
+            else:
+                random.Random(self.run_order_seed).shuffle(run_order)
+            self._run_generator = iter([run_args[index] for index in run_order])
 
         # Select backend executor
-        if self.backend in [Backend.RAY, Backend.RAY_REMOTE]:
+        if self.debug_mode:
+            # Runs are executed in the calling process, where breakpoints can be set
+            from radcad.backends.single_process import ExecutorSingleProcess as Executor
+        elif self.backend in [Backend.RAY, Backend.RAY_REMOTE]:
             if self.backend == Backend.RAY_REMOTE:
                 from radcad.extensions.backends.ray import ExecutorRayRemote as Executor
             else:
@@ -74,23 +240,165 @@ class Engine:
             from radcad.backends.multiprocessing import ExecutorMultiprocessing as Executor
         elif self.backend in [Backend.SINGLE_PROCESS]:
             from radcad.backends.single_process import ExecutorSingleProcess as Executor
+        elif self.backend in [Backend.THREADS]:
+            from radcad.backends.threads import ExecutorThreads as Executor
         else:
             raise Exception(f"Execution backend must be one of {Backend._member_names_}, not {self.backend}")
         
         result = Executor(self).execute_runs()
-        
-        self.executable.results, self.executable.exceptions = extract_exceptions(result)
+        if run_order:
+            # Results are returned in sequential order
+            ordered_result = [None] * len(run_order)
+            for (position, run_result) in enumerate(result):
+                ordered_result[run_order[position]] = run_result
+            result = ordered_result
+
+        if ndjson_path:
+            # States are written as the results of each run are collected, rather than retained, returning the number of states
+            exceptions = []
+            def states():
+                for (run_results, exception) in result:
+                    exceptions.append(exception)
+                    yield from flatten(run_results)
+            count = write_ndjson(states(), ndjson_path)
+            self.executable.results, self.executable.exceptions = [], exceptions
+            self.executable.timed_out = self._skipped_runs or any(
+                isinstance(run, dict) and run.get("timed_out", False) for run in exceptions
+            )
+            self.executable._after_experiment(experiment=(executable if isinstance(executable, wrappers.Experiment) else None))
+            return count
+
+        self.executable.results, self.executable.exceptions = extract_exceptions(
+            result, self.spill_threshold, self.compression, self.sort_columns, max_memory_mb, self.melt
+        )
+        self.executable.timed_out = self._skipped_runs or any(
+            isinstance(run, dict) and run.get("timed_out", False) for run in self.executable.exceptions
+        )
+        if isinstance(executable, wrappers.Experiment) and executable.reconcile_schema:
+            self.executable.results = reconcile_schema(
+                self.executable.results, executable.reconcile_schema == "fill", executable.fill_value
+            )
+        if self.profile:
+            self.executable.profile = core.merge_profiles(
+                [run["profile"] for run in self.executable.exceptions if isinstance(run, dict) and "profile" in run]
+            )
+        if parquet_path:
+            # Column types are inferred from the initial state of each Simulation
+            schema = {}
+            for simulation in simulations:
+                for (key, column_type) in simulation.result_schema().items():
+                    schema.setdefault(key, column_type)
+            write_parquet(self.executable.results, parquet_path, schema)
+        self.executable._after_experiment(experiment=(executable if isinstance(executable, wrappers.Experiment) else None))
+        return self.executable._postprocess(self.executable.results)
+
+    def _plan(self, simulations, only=None):
+        # The run plan is resolved from the parameter sweep of each Simulation, without calling any hooks or Model functions
+        only = None if only is None else set(map(tuple, only))
+        plan = []
+        for (simulation_index, simulation) in enumerate(simulations):
+            if simulation.param_matrix:
+                subset_runs = [row["runs"] for row in simulation.param_matrix]
+            else:
+                subset_runs = [simulation.runs] * len(simulation.parameter_sweep())
+            units = [
+                (run_index, subset_index)
+                for (subset_index, runs) in enumerate(subset_runs)
+                for run_index in range(runs)
+                if only is None or (run_index, subset_index) in only or (simulation_index, run_index, subset_index) in only
+            ]
+
+            drop_substeps = self.drop_substeps if simulation.drop_substeps is None else simulation.drop_substeps
+            substeps = 1 if drop_substeps else (simulation.substeps_per_timestep or len(simulation.model.state_update_blocks))
+            recorded_timesteps = simulation.timesteps
+            if self.record_stride:
+                # Timesteps on the stride are recorded, along with the final timestep
+                recorded_timesteps = -(-simulation.timesteps // self.record_stride)
+            history = sum(map(len, simulation.initial_history or []))
+            # An upper bound on the records of each run, as record_when, a terminating_condition, and the initial_substep
+            # can only reduce the records, and depend on the states of the run
+            run_records = 1 if self.final_only else history + 1 + recorded_timesteps * substeps
+
+            plan.append({
+                "simulation": simulation_index,
+                # Runs of every subset, which may differ between the rows of a param_matrix
+                "runs": sum(subset_runs),
+                "subsets": len(subset_runs),
+                "units": len(units),
+                "timesteps": simulation.timesteps,
+                "records": run_records * len(units),
+            })
+        return {
+            "simulations": len(simulations),
+            "units": sum(simulation["units"] for simulation in plan),
+            "timesteps": sum(simulation["timesteps"] * simulation["units"] for simulation in plan),
+            "records": sum(simulation["records"] for simulation in plan),
+            "plan": plan,
+        }
+
+    def _stream(self, executable=None, only=None):
+        if not executable:
+            raise Exception("Experiment or simulation required as Executable argument")
+        self.executable = executable
+
+        simulations = executable.simulations if isinstance(executable, wrappers.Experiment) else [executable]
+        configs = [Engine._get_config(sim) for sim in simulations]
+        only = None if only is None else set(map(tuple, only))
+
+        self.executable._before_experiment(experiment=(executable if isinstance(executable, wrappers.Experiment) else None))
+        # Runs are executed in the calling process, yielding each state as its substep completes
+        for run_args in self._run_stream(self.executable, configs, only):
+            _, exception, _, _ = yield from core.iter_single_run(*tuple(run_args))
+            if exception and self.raise_exceptions:
+                raise exception
         self.executable._after_experiment(experiment=(executable if isinstance(executable, wrappers.Experiment) else None))
-        return self.executable.results
+
+    def _get_config(sim):
+        return (
+            sim.model.initial_state,
+            sim.model.state_update_blocks,
+            sim.model.params,
+            sim.timesteps,
+            sim.runs,
+            {
+                "initial_history": sim.initial_history,
+                "run_params": sim.run_params,
+                "record_wall_time": sim.record_wall_time,
+                "initial_substep": sim.initial_substep,
+                "start_timestep": sim.start_timestep,
+                "substeps_per_timestep": sim.substeps_per_timestep,
+                "sweep": sim.sweep,
+                "state_schema": sim.state_schema,
+                "validators": sim.validators,
+                "param_matrix": sim.param_matrix,
+                "param_sets": sim.param_sets,
+                "after_substep": sim.after_substep,
+                "terminating_condition": sim.terminating_condition,
+                "record_when": sim.record_when,
+                "cumulative": sim.cumulative,
+                "deepcopy_keys": sim.deepcopy_keys,
+                "deltas": sim.deltas,
+                "run_metadata": sim.run_metadata,
+                "drop_substeps": sim.drop_substeps,
+                "serializer": sim.serializer,
+            },
+            sim.plan,
+            sim.parameter_sweep(),
+        )
 
     def _get_simulation_from_config(config):
-        states, state_update_blocks, params, timesteps, runs = config
-        model = wrappers.Model(
+        states, state_update_blocks, params, timesteps, runs, options, plan, param_sweep = config
+        # A compiled Simulation reuses the Model of its plan
+        model = plan.model if plan else wrappers.Model(
             initial_state=states, state_update_blocks=state_update_blocks, params=params
         )
-        return wrappers.Simulation(model=model, timesteps=timesteps, runs=runs)
+        simulation = wrappers.Simulation(model=model, timesteps=timesteps, runs=runs, **options)
+        simulation.plan = plan
+        simulation._param_sweep_cache = (model, model.params, simulation.sweep, param_sweep)
+        return simulation
 
-    def _run_stream(self, configs):
+    def _run_stream(self, executable, configs, only=None, deadline=None, max_memory_mb=None):
+        # The hooks of the executable are passed explicitly, rather than read from the Engine, e.g. for a SimulationStepper
         simulations = [Engine._get_simulation_from_config(config) for config in configs]
 
         for simulation_index, simulation in enumerate(simulations):
@@ -101,47 +409,124 @@ class Engine:
             initial_state = simulation.model.initial_state
             state_update_blocks = simulation.model.state_update_blocks
             params = simulation.model.params
-            param_sweep = core.generate_parameter_sweep(params)
+            if simulation.param_matrix:
+                param_sweep = [{k: v for k, v in row.items() if k not in ["runs", "seed"]} for row in simulation.param_matrix]
+                subset_runs = [row["runs"] for row in simulation.param_matrix]
+                # A row's seed replaces the Engine seed for the runs of that subset
+                subset_seeds = [row.get("seed", self.seed) for row in simulation.param_matrix]
+            else:
+                param_sweep = simulation.parameter_sweep()
+                subset_runs = [runs] * len(param_sweep)
+                subset_seeds = [self.seed] * len(param_sweep)
+            run_params = simulation.run_params
+            drop_substeps = self.drop_substeps if simulation.drop_substeps is None else simulation.drop_substeps
 
-            self.executable._before_simulation(
+            scenario_keys = [f"{key}_scenario" for (key, value) in params.items() if isinstance(value, core.Scenarios)]
+
+            if run_params and set(run_params) & set(params):
+                raise ValueError(f"Run parameters {sorted(set(run_params) & set(params))} can't also be swept parameters")
+
+            executable._before_simulation(
                 simulation=simulation
             )
 
             # NOTE Hook allows mutation of RunArgs
             for run_index in range(0, runs):
+                # The run index passed to hooks is zero-based unless `one_based_run` is enabled
+                hook_run = run_index + 1 if self.one_based_run else run_index
                 context = wrappers.Context(
                     simulation_index,
-                    run_index,
+                    hook_run,
                     None,
                     timesteps,
                     initial_state,
                     params  # NOTE Each parameter is a list of all subsets in before_run() method and a single subset in before_subset()
                 )
-                self.executable._before_run(context=context)
-                for subset_index, param_set in enumerate(param_sweep if param_sweep else [params]):
+                executable._before_run(context=context)
+                for subset_index, param_set in enumerate(param_sweep):
+                    if run_index >= subset_runs[subset_index]:
+                        continue
+                    if only is not None and (run_index, subset_index) not in only and (simulation_index, run_index, subset_index) not in only:
+                        continue
+                    if deadline and time.time() > deadline:
+                        self._skipped_runs = True
+                        continue
+                    if run_params:
+                        param_set = {**param_set, **core.select_run_params(run_params, run_index)}
                     context = wrappers.Context(
                         simulation_index,
-                        run_index,
+                        hook_run,
                         subset_index,
                         timesteps,
                         initial_state,
                         param_set
                     )
-                    self.executable._before_subset(context=context)
+                    executable._before_subset(context=context)
+                    run_metadata = simulation.run_metadata(
+                        simulation=simulation_index, run=hook_run, subset=subset_index, param_set=param_set
+                    ) if simulation.run_metadata else None
+                    if scenario_keys:
+                        # Records are labelled with the name of the scenario of each Scenarios parameter
+                        run_metadata = {**{key: param_set[key] for key in scenario_keys}, **(run_metadata or {})}
                     yield wrappers.RunArgs(
-                        simulation_index,
-                        timesteps,
-                        run_index,
-                        subset_index,
-                        copy.deepcopy(initial_state),
-                        state_update_blocks,
-                        copy.deepcopy(param_set),
-                        self.deepcopy,
-                        self.drop_substeps,
+                        simulation=simulation_index,
+                        timesteps=timesteps,
+                        run=run_index,
+                        subset=subset_index,
+                        initial_state=copy.deepcopy(initial_state),
+                        state_update_blocks=state_update_blocks,
+                        parameters=param_set if self.params_immutable else copy.deepcopy(param_set),
+                        deepcopy=self.deepcopy,
+                        drop_substeps=drop_substeps,
+                        options=core.RunOptions(
+                            final_only=self.final_only,
+                            fast_list_copy=self.fast_list_copy,
+                            run_uuid=self.run_uuid,
+                            check_finite=self.check_finite,
+                            serializer=simulation.serializer,
+                            initial_history=copy.deepcopy(simulation.initial_history),
+                            tracer=self.tracer,
+                            check_policy_mutation=self.check_policy_mutation,
+                            record_wall_time=simulation.record_wall_time,
+                            profile=self.profile,
+                            initial_substep=simulation.initial_substep,
+                            strict_schema=self.strict_schema,
+                            after_substep=simulation.after_substep,
+                            capture_stdout=self.capture_stdout,
+                            start_timestep=simulation.start_timestep,
+                            run_metadata=run_metadata,
+                            record_stride=self.record_stride,
+                            record_updated=self.record_updated,
+                            one_based_run=self.one_based_run,
+                            allow_stop=self.allow_stop,
+                            state_schema=simulation.state_schema,
+                            policies_only=self.policies_only,
+                            allow_new_keys=self.allow_new_keys,
+                            seed=subset_seeds[subset_index],
+                            on_run_finished=executable.on_run_finished,
+                            gc_interval=self.gc_interval,
+                            substeps_per_timestep=simulation.substeps_per_timestep,
+                            frozen_state=self.frozen_state,
+                            record_signals=self.record_signals,
+                            pin_cores=self.pin_cores,
+                            deadline=deadline,
+                            validators=simulation.validators,
+                            terminating_condition=simulation.terminating_condition,
+                            scratch=self.scratch,
+                            record_when=simulation.record_when,
+                            duplicate_updates=self.duplicate_updates,
+                            cumulative=simulation.cumulative,
+                            check_signal_finite=self.check_signal_finite,
+                            seed_substeps=self.seed_substeps,
+                            deepcopy_keys=simulation.deepcopy_keys,
+                            deltas=simulation.deltas,
+                            max_memory_mb=max_memory_mb,
+                            manage_lifecycle=self.manage_lifecycle,
+                        ),
                     )
-                    self.executable._after_subset(context=context)
-                self.executable._after_run(context=context)
+                    executable._after_subset(context=context)
+                executable._after_run(context=context)
 
-            self.executable._after_simulation(
+            executable._after_simulation(
                 simulation=simulation
             )
@@ -0,0 +1,92 @@
+from radcad.core import _normalize_psu, generate_parameter_sweep
+
+from array import array
+from collections.abc import Mapping
+import numbers
+
+
+def _infer_typecode(key, value) -> str:
+    if isinstance(value, bool) or not isinstance(value, numbers.Real):
+        raise TypeError(f"State Variable {key} must be numeric, not {type(value).__name__}")
+    return "q" if isinstance(value, numbers.Integral) else "d"
+
+
+class StateRow(Mapping):
+    """
+    A read-only view of the State Variables of a single substate, backed by the columns of a numeric run.
+    """
+
+    __slots__ = ("_columns", "_index")
+
+    def __init__(self, columns: dict, index: int):
+        self._columns = columns
+        self._index = index
+
+    def __getitem__(self, key):
+        return self._columns[key][self._index]
+
+    def __iter__(self):
+        return iter(self._columns)
+
+    def __len__(self):
+        return len(self._columns)
+
+
+def run_numeric(initial_state: dict, state_update_blocks: list, params: dict={}, timesteps: int=1, dtypes: dict={}) -> dict:
+    """
+    Executes a single run of a purely numeric model, storing each State Variable as a column preallocated
+    for every substep, rather than copying a dict of State Variables each substep.
+
+    Policy Functions are called with `(params, substep, previous_state)`, and State Update Functions with
+    `(params, substep, previous_state, policy_input)`, returning the updated value of the State Variable,
+    where `previous_state` is a read-only `StateRow` view of the previous substate.
+
+    Args:
+        initial_state (dict): Mapping of State Variable to its initial numeric value.
+        state_update_blocks (list): Partial State Update Blocks, as for a Model.
+        params (dict): A single parameter subset. Defaults to `{}`.
+        timesteps (int): Number of timesteps. Defaults to `1`.
+        dtypes (dict): Mapping of State Variable to an `array` module typecode, e.g. `"d"` or `"q"`.
+            Defaults to `"q"` for integer and `"d"` for other initial values.
+
+    Returns:
+        A dict of State Variable to an `array` of its value at each substep, along with `timestep` and `substep` columns.
+    """
+    param_sweep = generate_parameter_sweep(params)
+    if len(param_sweep) > 1:
+        raise ValueError("Numeric runs don't support parameter sweeps")
+    params = param_sweep[0]
+    state_update_blocks = list(map(_normalize_psu, state_update_blocks))
+    for psu in state_update_blocks:
+        for key in psu["variables"]:
+            if key not in initial_state:
+                raise KeyError(f"Invalid state key {key} in partial state update block")
+
+    # Columns are preallocated with the initial state for the initial state and every substep
+    rows = 1 + timesteps * len(state_update_blocks)
+    columns = {
+        key: array(dtypes.get(key) or _infer_typecode(key, value), [value]) * rows
+        for (key, value) in initial_state.items()
+    }
+    timestep_column = array("q", [0]) * rows
+    substep_column = array("q", [0]) * rows
+
+    row = 0
+    for timestep in range(1, timesteps + 1):
+        # As in the Engine, functions are passed the zero-based substep, while the recorded substep is one-based
+        for (substep, psu) in enumerate(state_update_blocks):
+            previous_state = StateRow(columns, row)
+            signals = {}
+            for policy in psu["policies"].values():
+                for (key, value) in policy(params, substep, previous_state).items():
+                    signals[key] = signals[key] + value if key in signals else value
+
+            row += 1
+            for column in columns.values():
+                column[row] = column[row - 1]
+            for (key, function) in psu["variables"].items():
+                columns[key][row] = function(params, substep, previous_state, signals)
+            timestep_column[row] = timestep
+            substep_column[row] = substep + 1
+
+    return {**columns, "timestep": timestep_column, "substep": substep_column}
@@ -0,0 +1,140 @@
+from collections.abc import Sequence
+import bz2
+import gzip
+import lzma
+import pickle
+import tempfile
+
+
+def _zstd_codec():
+    import zstandard
+    return (zstandard.ZstdCompressor().compress, zstandard.ZstdDecompressor().decompress)
+
+
+# Compression codecs, as (compress, decompress) functions
+COMPRESSION_CODECS = {
+    "gzip": lambda: (gzip.compress, gzip.decompress),
+    "bz2": lambda: (bz2.compress, bz2.decompress),
+    "lzma": lambda: (lzma.compress, lzma.decompress),
+    # Requires the zstandard package
+    "zstd": _zstd_codec,
+}
+
+
+class SpilledResults(Sequence):
+    """
+    A memory-bounded sequence of simulation results, that buffers up to `spill_threshold` states in memory
+    and spills older states to a temporary file, read back lazily on access.
+    Spilled states are optionally compressed using one of the `COMPRESSION_CODECS`.
+    """
+
+    def __init__(self, spill_threshold: int, compression: str=None):
+        if spill_threshold < 1:
+            raise ValueError("Spill threshold must be a positive number of states")
+        if compression is not None and compression not in COMPRESSION_CODECS:
+            raise ValueError(f"Compression must be one of {list(COMPRESSION_CODECS)}, not {compression}")
+        self.spill_threshold = spill_threshold
+        self.compression = compression
+        self._compress, self._decompress = COMPRESSION_CODECS[compression]() if compression else (None, None)
+        self._buffer = []
+        self._file = None
+        # Offset in the spill file, number of states, and number of bytes, of each spilled chunk
+        self._chunks = []
+        self._spilled_length = 0
+
+    def append(self, state):
+        self._buffer.append(state)
+        if len(self._buffer) >= self.spill_threshold:
+            self._spill()
+
+    def extend(self, states):
+        for state in states:
+            self.append(state)
+
+    def spill(self):
+        """
+        Spills the buffered states to disk before the spill threshold is reached, e.g. to free memory.
+        """
+        if self._buffer:
+            self._spill()
+
+    def _spill(self):
+        if self._file is None:
+            self._file = tempfile.TemporaryFile()
+        self._file.seek(0, 2)
+        data = pickle.dumps(self._buffer, -1)
+        if self._compress:
+            data = self._compress(data)
+        self._chunks.append((self._file.tell(), len(self._buffer), len(data)))
+        self._file.write(data)
+        self._spilled_length += len(self._buffer)
+        self._buffer = []
+
+    def _load_chunk(self, offset, size):
+        self._file.seek(offset)
+        data = self._file.read(size)
+        if self._decompress:
+            data = self._decompress(data)
+        return pickle.loads(data)
+
+    def __len__(self):
+        return self._spilled_length + len(self._buffer)
+
+    def __iter__(self):
+        for (offset, _, size) in self._chunks:
+            yield from self._load_chunk(offset, size)
+        yield from self._buffer
+
+    def _locate(self, index):
+        # Index of the spilled chunk containing a state, and the index of the state within the chunk
+        for (chunk, (_, length, _)) in enumerate(self._chunks):
+            if index < length:
+                return chunk, index
+            index -= length
+
+    def __getitem__(self, index):
+        if isinstance(index, slice):
+            # The indices of a slice are monotonic, so each spilled chunk is loaded once, when first reached
+            states = []
+            loaded, chunk_states = None, None
+            for i in range(*index.indices(len(self))):
+                if i >= self._spilled_length:
+                    states.append(self._buffer[i - self._spilled_length])
+                    continue
+                (chunk, position) = self._locate(i)
+                if chunk != loaded:
+                    (offset, _, size) = self._chunks[chunk]
+                    loaded, chunk_states = chunk, self._load_chunk(offset, size)
+                states.append(chunk_states[position])
+            return states
+        if index < 0:
+            index += len(self)
+        if not 0 <= index < len(self):
+            raise IndexError("SpilledResults index out of range")
+        if index >= self._spilled_length:
+            return self._buffer[index - self._spilled_length]
+        (chunk, position) = self._locate(index)
+        (offset, _, size) = self._chunks[chunk]
+        return self._load_chunk(offset, size)[position]
+
+    def to_list(self):
+        """
+        Reads back the complete sequence of states into memory.
+        """
+        return list(self)
+
+    def close(self):
+        if self._file is not None:
+            self._file.close()
+            self._file = None
+        self._buffer = []
+        self._chunks = []
+        self._spilled_length = 0
+
+    def __getstate__(self):
+        # Pickling materializes the spilled states, e.g. when a Simulation is deep copied
+        return {"spill_threshold": self.spill_threshold, "compression": self.compression, "states": self.to_list()}
+
+    def __setstate__(self, state):
+        self.__init__(state["spill_threshold"], state.get("compression", None))
+        self.extend(state["states"])
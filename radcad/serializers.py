@@ -0,0 +1,82 @@
+import copy
+import pickle
+
+try:
+    import numpy
+except ImportError:
+    numpy = None
+
+
+class Serializer(object):
+    """
+    Serializes and deserializes State Variables, used to deep copy the state passed to Policy and State Update Functions.
+    """
+
+    def dumps(self, obj):
+        raise NotImplementedError("Method dumps() not implemented for class that extends Serializer")
+
+    def loads(self, data):
+        raise NotImplementedError("Method loads() not implemented for class that extends Serializer")
+
+    def copy(self, obj):
+        """
+        Deep copies an object, by default serializing and deserializing it.
+        """
+        return self.loads(self.dumps(obj))
+
+
+class PickleSerializer(Serializer):
+    def __init__(self, protocol: int=-1):
+        """
+        Args:
+            protocol (int): Pickle protocol to use. Defaults to `-1`, the highest protocol available.
+                From protocol 5, buffers such as numpy arrays are copied out-of-band, rather than through the pickle.
+        """
+        self.protocol = protocol
+
+    def dumps(self, obj):
+        return pickle.dumps(obj, self.protocol)
+
+    def loads(self, data):
+        return pickle.loads(data)
+
+    def copy(self, obj):
+        protocol = pickle.HIGHEST_PROTOCOL if self.protocol < 0 else self.protocol
+        if protocol < 5:
+            return super().copy(obj)
+        # Buffers, e.g. of numpy arrays, are passed out-of-band, so are copied once rather than into and out of the pickle
+        buffers = []
+        data = pickle.dumps(obj, protocol, buffer_callback=buffers.append)
+        return pickle.loads(data, buffers=[bytearray(buffer.raw()) for buffer in buffers])
+
+
+class DeepcopySerializer(Serializer):
+    """
+    Deep copies State Variables using `copy.deepcopy()`, for State Variables that can't be pickled.
+    """
+
+    def dumps(self, obj):
+        return copy.deepcopy(obj)
+
+    def loads(self, data):
+        return data
+
+    def copy(self, obj):
+        return copy.deepcopy(obj)
+
+
+class NumpySerializer(PickleSerializer):
+    """
+    Copies State Variables that are numpy arrays of non-object dtype using `ndarray.copy()`, and pickles the remaining State Variables.
+    """
+
+    def copy(self, obj):
+        if numpy is None or not isinstance(obj, dict):
+            return super().copy(obj)
+
+        arrays = {
+            key: value.copy() for key, value in obj.items()
+            if isinstance(value, numpy.ndarray) and value.dtype != object
+        }
+        copied = super().copy({key: value for key, value in obj.items() if key not in arrays})
+        return {key: arrays[key] if key in arrays else copied[key] for key in obj}
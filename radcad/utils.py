@@ -1,4 +1,15 @@
+from radcad.core import check_memory_budget, memory_usage_mb
+from radcad.results import SpilledResults
+
 import itertools
+import json
+import numbers
+import random
+
+try:
+    import numpy
+except ImportError:
+    numpy = None
 
 
 def flatten(nested_list):
@@ -13,12 +24,455 @@ def flatten(nested_list):
     return list(generator(nested_list))
 
 
-def extract_exceptions(results_with_exceptions):
+def _sort_columns(state: dict) -> dict:
+    return dict(sorted(state.items()))
+
+
+def melt_states(states, keys):
+    """
+    Melts the given State Variables of each state into long-format rows, for plotting libraries that expect long-format data,
+    with a row per state and State Variable, containing the remaining columns of the state as index columns,
+    the name of the State Variable as `variable`, and its value as `value`.
+
+    Args:
+        states (Iterable[dict]): States, e.g. the results of a Simulation or Experiment.
+        keys (list): Numeric State Variables to melt.
+
+    Returns:
+        A generator of long-format rows.
+    """
+    keys = list(keys)
+    for state in states:
+        index = {key: value for (key, value) in state.items() if key not in keys}
+        for key in keys:
+            value = state[key]
+            if isinstance(value, bool) or not isinstance(value, numbers.Number):
+                raise TypeError(f"Melted State Variable {key} must be numeric, not {type(value).__name__}")
+            yield {**index, "variable": key, "value": value}
+
+
+def _within_memory_budget(results_with_exceptions, max_memory_mb):
+    for run in results_with_exceptions:
+        yield run
+        check_memory_budget(max_memory_mb, "collecting results, consider using the spill_threshold Engine option")
+
+
+def extract_exceptions(results_with_exceptions, spill_threshold=None, compression=None, sort_columns=False, max_memory_mb=None, melt=None):
+    if spill_threshold:
+        # Results of each run are spilled to disk as they're collected
+        results, exceptions = SpilledResults(spill_threshold, compression), []
+        for (run_results, exception) in results_with_exceptions:
+            run_results = flatten(run_results)
+            run_results = list(map(_sort_columns, run_results)) if sort_columns else run_results
+            results.extend(melt_states(run_results, melt) if melt else run_results)
+            exceptions.append(exception)
+            if max_memory_mb and memory_usage_mb() > max_memory_mb:
+                # Buffered states are spilled early when over the memory budget
+                results.spill()
+        return (results, exceptions)
+
+    if max_memory_mb:
+        results_with_exceptions = _within_memory_budget(results_with_exceptions, max_memory_mb)
+    results_with_exceptions = list(results_with_exceptions)
+    if not results_with_exceptions:
+        # No runs were executed, e.g. when the timeout expired before the first run, or `only` matched no runs
+        return ([], [])
     results, exceptions = zip(*results_with_exceptions)
-    return (list(flatten(flatten(list(results)))), list(exceptions))
+    results = flatten(flatten(list(results)))
+    # Columns are ordered as the initial state, unless sorted alphabetically
+    results = list(map(_sort_columns, results)) if sort_columns else list(results)
+    # Long-format rows are assembled from the states, rather than melted once the results are returned
+    return (list(melt_states(results, melt)) if melt else results, list(exceptions))
 
 
 def generate_cartesian_product_parameter_sweep(params):
     cartesian_product = list(itertools.product(*params.values()))
     param_sweep = {key: [x[i] for x in cartesian_product] for i, key in enumerate(params.keys())}
     return param_sweep
+
+
+# Sobol sequence direction numbers (dimension 2 onwards) from S. Joe and F. Y. Kuo, "Constructing Sobol sequences with better two-dimensional projections"
+# Each entry is (s, a, m): the degree and coefficients of the primitive polynomial, and the initial direction numbers
+_SOBOL_DIRECTION_NUMBERS = [
+    (1, 0, [1]),
+    (2, 1, [1, 3]),
+    (3, 1, [1, 3, 1]),
+    (3, 2, [1, 1, 1]),
+    (4, 1, [1, 1, 3, 3]),
+    (4, 4, [1, 3, 5, 13]),
+    (5, 2, [1, 1, 5, 5, 17]),
+    (5, 4, [1, 1, 5, 5, 5]),
+    (5, 7, [1, 1, 7, 11, 19]),
+    (5, 11, [1, 1, 5, 1, 1]),
+    (5, 13, [1, 1, 1, 3, 11]),
+    (5, 14, [1, 3, 5, 5, 31]),
+    (6, 1, [1, 3, 3, 9, 7, 49]),
+    (6, 13, [1, 1, 1, 15, 21, 21]),
+    (6, 16, [1, 3, 1, 13, 27, 49]),
+]
+_SOBOL_BITS = 30
+
+
+def _sobol_direction_vectors(dimension):
+    if dimension == 0:
+        return [1 << (_SOBOL_BITS - i) for i in range(1, _SOBOL_BITS + 1)]
+
+    s, a, m = _SOBOL_DIRECTION_NUMBERS[dimension - 1]
+    v = [m[i - 1] << (_SOBOL_BITS - i) for i in range(1, s + 1)]
+    for i in range(s + 1, _SOBOL_BITS + 1):
+        value = v[i - s - 1] ^ (v[i - s - 1] >> s)
+        for k in range(1, s):
+            value ^= ((a >> (s - 1 - k)) & 1) * v[i - k - 1]
+        v.append(value)
+    return v
+
+
+def _sobol_samples(n_samples, dimensions, rng):
+    if dimensions > len(_SOBOL_DIRECTION_NUMBERS) + 1:
+        raise ValueError(f"Sobol sampling supports at most {len(_SOBOL_DIRECTION_NUMBERS) + 1} parameters")
+
+    direction_vectors = [_sobol_direction_vectors(dimension) for dimension in range(dimensions)]
+    # Random digital shift, to scramble the sequence reproducibly when seeded
+    shifts = [rng.getrandbits(_SOBOL_BITS) if rng else 0 for _ in range(dimensions)]
+    x = [0] * dimensions
+    samples = []
+    for index in range(n_samples):
+        samples.append([(x[d] ^ shifts[d]) / (1 << _SOBOL_BITS) for d in range(dimensions)])
+        # Gray code ordering: update using the direction vector of the rightmost zero bit of the index
+        c = ((~index) & (index + 1)).bit_length() - 1
+        x = [x[d] ^ direction_vectors[d][c] for d in range(dimensions)]
+    return samples
+
+
+def _latin_hypercube_samples(n_samples, dimensions, rng):
+    columns = []
+    for _ in range(dimensions):
+        strata = list(range(n_samples))
+        rng.shuffle(strata)
+        columns.append([(stratum + rng.random()) / n_samples for stratum in strata])
+    return [list(sample) for sample in zip(*columns)]
+
+
+def generate_sampled_sweep(param_ranges, method="lhs", n_samples=10, seed=None):
+    """
+    Generates a parameter sweep of `n_samples` parameter subsets using quasi-random sampling,
+    for use as Model params like any other parameter sweep.
+
+    Args:
+        param_ranges (dict): Mapping of parameter key to a `(low, high)` range to sample from.
+        method (str): Either `"lhs"` for Latin hypercube sampling, or `"sobol"` for a Sobol sequence. Defaults to `"lhs"`.
+        n_samples (int): Number of parameter subsets to generate. Defaults to `10`.
+        seed (int, optional): Random seed, for reproducible Latin hypercube sampling and Sobol sequence scrambling. Defaults to `None`.
+    """
+    rng = random.Random(seed)
+    dimensions = len(param_ranges)
+    if method == "lhs":
+        samples = _latin_hypercube_samples(n_samples, dimensions, rng)
+    elif method == "sobol":
+        samples = _sobol_samples(n_samples, dimensions, rng if seed is not None else None)
+    else:
+        raise ValueError(f"Sampling method must be one of ['lhs', 'sobol'], not {method}")
+
+    return {
+        key: [low + sample[d] * (high - low) for sample in samples]
+        for d, (key, (low, high)) in enumerate(param_ranges.items())
+    }
+
+
+def generate_oat_sweep(baseline, perturbations):
+    """
+    Generates a one-at-a-time (OAT) parameter sweep for local sensitivity analysis, for use as Model params
+    like any other parameter sweep. The first parameter subset is the baseline, followed by a parameter subset
+    for each perturbation value of each parameter, holding the other parameters at their baseline value.
+
+    Args:
+        baseline (dict): Mapping of parameter key to its baseline value.
+        perturbations (dict): Mapping of parameter key to a list of values to vary the parameter through.
+    """
+    if perturbations.keys() - baseline.keys():
+        raise KeyError(f"Perturbed parameters {sorted(perturbations.keys() - baseline.keys())} aren't in the baseline")
+
+    param_sets = [baseline] + [
+        {**baseline, key: value}
+        for (key, values) in perturbations.items()
+        for value in values
+    ]
+    return {key: [param_set[key] for param_set in param_sets] for key in baseline}
+
+
+def merge_results(results, reindex=True):
+    """
+    Concatenates the results of multiple simulation runs, e.g. batches of Monte Carlo runs executed on separate machines.
+
+    Args:
+        results (list): A list of simulation results, each a list of states.
+        reindex (bool): Whether to renumber the `run` index of each batch of results to follow the highest `run` index
+            of the same `simulation` in the preceding batches, so that indices remain unique, whether one-based or zero-based.
+            Defaults to `True`.
+    """
+    if not reindex:
+        return [state for batch in results for state in batch]
+
+    merged = []
+    # Highest run index of each simulation in the preceding batches
+    last_runs = {}
+    for batch in results:
+        first_runs = {}
+        for state in batch:
+            first_runs[state["simulation"]] = min(first_runs.get(state["simulation"], state["run"]), state["run"])
+        batch_runs = {}
+        for state in batch:
+            simulation = state["simulation"]
+            run = state["run"]
+            if simulation in last_runs:
+                run += last_runs[simulation] + 1 - first_runs[simulation]
+            batch_runs[simulation] = max(batch_runs.get(simulation, run), run)
+            merged.append({**state, "run": run})
+        last_runs.update(batch_runs)
+    return merged
+
+
+def reconcile_schema(results, fill=False, fill_value=None):
+    """
+    Checks that the states of every simulation have the same keys, e.g. when combining the results of Simulations
+    with different State Variables, to avoid misaligned columns.
+
+    Args:
+        results (list): Simulation results, a list of states.
+        fill (bool): Whether to fill missing keys with `fill_value`, rather than raising a `KeyError`
+            naming the mismatched keys. Defaults to `False`.
+        fill_value: Value of missing keys when `fill` is `True`. Defaults to `None`.
+    """
+    schemas = {}
+    for state in results:
+        schema = schemas.setdefault(state["simulation"], {})
+        schema.update(dict.fromkeys(state))
+    if len({tuple(sorted(schema)) for schema in schemas.values()}) <= 1:
+        return results
+
+    keys = {}
+    for schema in schemas.values():
+        keys.update(schema)
+    if not fill:
+        (first_simulation, first_schema), *other_schemas = schemas.items()
+        for (simulation, schema) in other_schemas:
+            if schema.keys() != first_schema.keys():
+                raise KeyError(
+                    f"State schema of simulation {simulation} doesn't match simulation {first_simulation}: "
+                    f"missing {sorted(first_schema.keys() - schema.keys())}, unexpected {sorted(schema.keys() - first_schema.keys())}"
+                )
+    return [{key: state.get(key, fill_value) for key in keys} for state in results]
+
+
+DIFF_INDEX_KEYS = ("simulation", "run", "subset", "timestep", "substep")
+
+
+def diff_results(a, b, keys=None):
+    """
+    Compares two sets of results, e.g. before and after a model refactor, aligning states by their
+    `(simulation, run, subset, timestep, substep)` index.
+
+    Args:
+        a (list): Simulation results, a list of states.
+        b (list): Simulation results to compare against `a`, a list of states.
+        keys (list, optional): State Variables to compare. Defaults to every key of either results, other than the index keys.
+
+    Returns:
+        A dict of:
+            `columns`: Mapping of each compared key to the number of `differing` states, and for numeric keys,
+                the `max_delta` and `mean_delta` of the absolute differences `b - a`.
+            `first_divergence`: The `index` of the first differing state of `a`, and its differing `keys`, or `None`.
+            `only_a` and `only_b`: The indices of states only in `a`, or only in `b`.
+    """
+    indexed_a = {tuple(state[key] for key in DIFF_INDEX_KEYS): state for state in a}
+    indexed_b = {tuple(state[key] for key in DIFF_INDEX_KEYS): state for state in b}
+    if keys is None:
+        keys = {}
+        for state in itertools.chain(indexed_a.values(), indexed_b.values()):
+            keys.update(dict.fromkeys(key for key in state if key not in DIFF_INDEX_KEYS))
+
+    columns = {key: {"differing": 0, "deltas": []} for key in keys}
+    first_divergence = None
+    for (index, state_a) in indexed_a.items():
+        state_b = indexed_b.get(index)
+        if state_b is None:
+            continue
+        differing_keys = []
+        for key in keys:
+            value_a, value_b = state_a.get(key), state_b.get(key)
+            if isinstance(value_a, numbers.Number) and isinstance(value_b, numbers.Number) and not isinstance(value_a, bool):
+                columns[key]["deltas"].append(abs(value_b - value_a))
+            if value_a != value_b:
+                columns[key]["differing"] += 1
+                differing_keys.append(key)
+        if differing_keys and first_divergence is None:
+            first_divergence = {"index": index, "keys": differing_keys}
+
+    for column in columns.values():
+        deltas = column.pop("deltas")
+        if deltas:
+            column.update({"max_delta": max(deltas), "mean_delta": sum(deltas) / len(deltas)})
+    return {
+        "columns": columns,
+        "first_divergence": first_divergence,
+        "only_a": [index for index in indexed_a if index not in indexed_b],
+        "only_b": [index for index in indexed_b if index not in indexed_a],
+    }
+
+
+INDEX_KEYS = ("simulation", "subset", "run", "timestep", "substep")
+
+
+def split_indices(results, index_keys=INDEX_KEYS):
+    """
+    Separates the index keys of each state from the State Variables, e.g. to store them in separate tables
+    of a normalized database schema. Can also be used as the `postprocess` hook of a Simulation or Experiment.
+
+    Args:
+        results (list): Simulation results, a list of states.
+        index_keys (tuple): Keys of each state to separate. Defaults to `(simulation, subset, run, timestep, substep)`.
+
+    Returns:
+        An `(indices, states)` tuple of two aligned lists, of dicts of the index keys of each state,
+        and of dicts of the remaining keys of each state.
+    """
+    indices, states = [], []
+    for state in results:
+        indices.append({key: state[key] for key in index_keys})
+        states.append({key: value for (key, value) in state.items() if key not in index_keys})
+    return (indices, states)
+
+
+DATASET_DIMS = ("run", "subset", "timestep", "substep")
+
+
+def to_dataset_dict(results):
+    """
+    Converts the results of a Simulation to arrays shaped `[run, subset, timestep, substep]` per numeric State Variable,
+    in the dictionary format accepted by `xarray.Dataset.from_dict()`. Substeps that weren't recorded,
+    e.g. substeps other than `0` of the initial state, are `NaN`.
+
+    Results with a non-uniform shape, e.g. runs of more than one Simulation, or runs that ended early,
+    are returned unchanged as a list of states.
+
+    Args:
+        results (list): Simulation results, a list of states.
+    """
+    results = list(results)
+    if not results or len({state["simulation"] for state in results}) > 1:
+        return results
+
+    coords = {dim: sorted({state[dim] for state in results}) for dim in DATASET_DIMS}
+    steps_per_run = {}
+    for state in results:
+        steps_per_run.setdefault((state["run"], state["subset"]), set()).add((state["timestep"], state["substep"]))
+    steps = set.union(*steps_per_run.values())
+    if len(steps_per_run) != len(coords["run"]) * len(coords["subset"]) or any(
+        run_steps != steps for run_steps in steps_per_run.values()
+    ):
+        return results
+
+    variables = [
+        key for key, value in results[0].items()
+        if key not in DATASET_DIMS + ("simulation",)
+        and all(isinstance(state.get(key), numbers.Number) for state in results)
+    ]
+    index = {dim: {value: i for i, value in enumerate(coords[dim])} for dim in DATASET_DIMS}
+    # Preallocate the arrays of each State Variable
+    data = {
+        key: [
+            [
+                [[float("nan")] * len(coords["substep"]) for _ in coords["timestep"]]
+                for _ in coords["subset"]
+            ]
+            for _ in coords["run"]
+        ]
+        for key in variables
+    }
+    for state in results:
+        run, subset, timestep, substep = (index[dim][state[dim]] for dim in DATASET_DIMS)
+        for key in variables:
+            data[key][run][subset][timestep][substep] = state[key]
+
+    return {
+        "coords": {dim: {"dims": dim, "data": coords[dim]} for dim in DATASET_DIMS},
+        "dims": {dim: len(coords[dim]) for dim in DATASET_DIMS},
+        "data_vars": {key: {"dims": DATASET_DIMS, "data": data[key]} for key in variables},
+    }
+
+
+def safe_div(a, b, default=0):
+    """
+    Divides `a` by `b`, returning `default` when `b` is zero, e.g. to guard against empty pools in Policy Functions.
+    numpy arrays are divided elementwise, with `default` wherever the denominator is zero.
+
+    Args:
+        a: Numerator.
+        b: Denominator.
+        default: Value returned when the denominator is zero. Defaults to `0`.
+    """
+    if numpy is not None and (isinstance(a, numpy.ndarray) or isinstance(b, numpy.ndarray)):
+        a, b = numpy.broadcast_arrays(numpy.asarray(a, dtype=float), numpy.asarray(b, dtype=float))
+        return numpy.divide(a, b, out=numpy.full(a.shape, default, dtype=float), where=b != 0)
+    return default if b == 0 else a / b
+
+
+def write_parquet(results, path, schema=None):
+    """
+    Writes results to a Parquet file, buffering the states as columns and writing a single table.
+    Requires the pyarrow package.
+
+    Args:
+        results (list): Simulation results, a list of states.
+        path (str): Path of the Parquet file.
+        schema (dict, optional): Ordered dict of column name to type, e.g. from `Simulation.result_schema()`.
+            `bool`, `int`, `float`, and `str` columns are typed accordingly, falling back to the type inferred
+            from the values, e.g. an `int` State Variable updated to a `float`. Additional keys in the results
+            are appended as columns with inferred types. Defaults to `None`, inferring every column type.
+    """
+    import pyarrow
+    import pyarrow.parquet
+
+    types = {bool: pyarrow.bool_(), int: pyarrow.int64(), float: pyarrow.float64(), str: pyarrow.string()}
+    schema = dict(schema or {})
+    results = list(results)
+    for state in results:
+        for key in state:
+            schema.setdefault(key, object)
+
+    columns = {}
+    for (key, column_type) in schema.items():
+        values = [state.get(key) for state in results]
+        try:
+            columns[key] = pyarrow.array(values, type=types.get(column_type))
+        except (pyarrow.ArrowInvalid, pyarrow.ArrowTypeError):
+            columns[key] = pyarrow.array(values)
+    pyarrow.parquet.write_table(pyarrow.table(columns), path)
+
+
+def _json_default(value):
+    # numpy scalars and arrays, and other values with a `tolist()` method, are converted to Python values
+    if hasattr(value, "tolist"):
+        return value.tolist()
+    raise TypeError(f"Object of type {type(value).__name__} is not JSON serializable")
+
+
+def write_ndjson(results, path):
+    """
+    Writes results to a newline-delimited JSON file, one state per line, consuming the results lazily
+    so that states don't need to be held in memory.
+
+    Args:
+        results (iterable): Simulation results, an iterable of states.
+        path (str): Path of the newline-delimited JSON file.
+
+    Returns:
+        The number of states written.
+    """
+    count = 0
+    with open(path, "w") as file:
+        for state in results:
+            file.write(json.dumps(state, default=_json_default))
+            file.write("\n")
+            count += 1
+    return count
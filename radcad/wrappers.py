@@ -1,7 +1,13 @@
-from radcad.core import _single_run_wrapper, generate_parameter_sweep
+from radcad.core import (
+    SIGNAL_PREFIX, TIMESTEP_END, Scenarios, _normalize_psu, _psu_params, _single_run_wrapper, _with_scratch, _wrap_state_update_blocks,
+    generate_parameter_sweep, iter_single_run, reduce_signals, select_run_params, validate_state_update_blocks,
+)
 from radcad.engine import Engine
+from radcad.backends import Backend
 from collections import namedtuple
+from functools import partial
 import copy
+import numbers
 
 
 RunArgs = namedtuple("RunArgs", [
@@ -14,8 +20,26 @@ RunArgs = namedtuple("RunArgs", [
     "parameters",
     "deepcopy",
     "drop_substeps",
-])
+    # The remaining Engine and Simulation options of the run, as RunOptions
+    "options",
+], defaults=(False, False, None))
 Context = namedtuple("Context", "simulation run subset timesteps initial_state parameters")
+# A validated copy of a Simulation's Model and its parameter sweep, reused by subsequent runs
+SimulationPlan = namedtuple("SimulationPlan", "model param_sweep")
+# Experiment parallelism strategies, and the Engine backend each dispatches to
+EXPERIMENT_BACKENDS = {
+    "serial": Backend.SINGLE_PROCESS,
+    "threads": Backend.THREADS,
+    "processes": Backend.DEFAULT,
+}
+
+
+def _params_unchanged(snapshot: dict, params: dict) -> bool:
+    try:
+        return bool(snapshot == params)
+    except Exception:
+        # Values that can't be compared as a single bool, e.g. numpy arrays, are never cached
+        return False
 
 
 class Model:
@@ -40,7 +64,7 @@ class Model:
     def __iter__(self):
         while True:
             param_sweep = generate_parameter_sweep(self.params)
-            _params = param_sweep[0] if param_sweep else {}
+            _params = param_sweep[0]
             run_args = RunArgs(
                 simulation = 0,
                 timesteps = 1,
@@ -71,6 +95,9 @@ class Executable:
 
         self.results = []
         self.exceptions = []
+        self.profile = {}
+        # Whether the most recent run was truncated by its timeout
+        self.timed_out = False
 
         # Hooks
         self.before_experiment = kwargs.pop("before_experiment", None)
@@ -81,6 +108,10 @@ class Executable:
         self.after_run = kwargs.pop("after_run", None)
         self.before_subset = kwargs.pop("before_subset", None)
         self.after_subset = kwargs.pop("after_subset", None)
+        # Transforms the results returned from run()
+        self.postprocess = kwargs.pop("postprocess", None)
+        # Called within the process executing each run once it completes, or fails, with the simulation, run, and subset indices and its duration in seconds
+        self.on_run_finished = kwargs.pop("on_run_finished", None)
 
     def __deepcopy__(self, memo={}):
         # Reset iterators to enable deepcopy after simulation run
@@ -96,6 +127,13 @@ class Executable:
     def run(self):
         raise NotImplementedError("Method run() not implemented for class that extends Base")
 
+    def stream(self, only=None):
+        """
+        Returns a generator of the recorded states of each timestep as it completes, the same as the results of `run()`,
+        e.g. for live plotting, executing runs in the calling process. States aren't retained, and the results of `run()` aren't updated.
+        """
+        return self.engine._stream(executable=self, only=only)
+
     # Hooks
     def _before_experiment(self, experiment=None):
         if self.before_experiment:
@@ -133,22 +171,324 @@ class Executable:
         if self.after_subset:
             self.after_subset(context=context)
 
+    def _postprocess(self, results):
+        if self.postprocess:
+            return self.postprocess(results)
+        return results
+
+
+def _positive_int(name: str, value) -> int:
+    # Integral values, e.g. numpy integers, are coerced to int, while zero, negative, and fractional values are rejected
+    if isinstance(value, bool) or not isinstance(value, numbers.Integral) or value < 1:
+        raise ValueError(f"Simulation {name} must be a positive integer, not {value!r}")
+    return int(value)
+
 
 class Simulation(Executable):
     def __init__(self, model: Model, timesteps=100, runs=1, **kwargs):
         super().__init__(**kwargs)
 
         self.model = model
-        self.timesteps = timesteps
-        self.runs = runs
+        self.timesteps = _positive_int("timesteps", timesteps)
+        self.runs = _positive_int("runs", runs)
 
         self.index = kwargs.pop("index", 0)
+        self.plan = None
+        # The Model, params, and sweep option the cached parameter sweep was generated from, and the parameter sweep
+        self._param_sweep_cache = None
+        self.initial_history = kwargs.pop("initial_history", None)
+        self.run_params = kwargs.pop("run_params", None)
+        self.record_wall_time = kwargs.pop("record_wall_time", False)
+        self.initial_substep = kwargs.pop("initial_substep", 0)
+        # Timestep of the initial state, unless the initial state, or initial history, has a non-zero timestep
+        self.start_timestep = kwargs.pop("start_timestep", 0)
+        # Number of substeps recorded per timestep, truncating or padding the substeps of each timestep
+        self.substeps_per_timestep = kwargs.pop("substeps_per_timestep", None)
+        # Whether to generate a parameter sweep, or use the params as a single parameter subset
+        self.sweep = kwargs.pop("sweep", True)
+        # Mapping of State Variable to its type, or allowed categorical values, checked after each update
+        self.state_schema = kwargs.pop("state_schema", None)
+        # Mapping of State Variable to a function called with each updated value, returning the value to store
+        self.validators = kwargs.pop("validators", None)
+        # Mapping of State Variable to a running aggregate over timesteps, one of "sum", "max", or "min", recorded as a `{key}_cum{aggregate}` column
+        self.cumulative = kwargs.pop("cumulative", None)
+        # State Variables to record the change of since the previous timestep, as a `{key}_delta` column
+        self.deltas = kwargs.pop("deltas", None)
+        # Explicit parameter subsets, one row per subset, used in place of the Model parameter sweep
+        self.param_matrix = kwargs.pop("param_matrix", None)
+        # Fully-specified parameter subsets, e.g. from an external sampler, used in place of the Model parameter sweep
+        self.param_sets = kwargs.pop("param_sets", None)
+        # Hook called within each run, after each substep
+        self.after_substep = kwargs.pop("after_substep", None)
+        # Called within each run with the final state of each timestep, ending the run early when it returns True
+        self.terminating_condition = kwargs.pop("terminating_condition", None)
+        # Called within each run with each substate, only recording the substates for which it returns True, along with the final state,
+        # truncating the state history seen by Policy and State Update Functions to the recorded substates
+        self.record_when = kwargs.pop("record_when", None)
+        # Called before each run with the simulation, run, subset, and param_set, returning a dict added to every state of the run
+        self.run_metadata = kwargs.pop("run_metadata", None)
+        # State Variables to deep copy each substep, referencing the other State Variables, which are treated as immutable
+        self.deepcopy_keys = kwargs.pop("deepcopy_keys", None)
+        # Serializer used to deep copy State Variables, with `dumps()` and `loads()` methods, defaulting to a `PickleSerializer`
+        self.serializer = kwargs.pop("serializer", None)
+        # Engine options that can be overridden per Simulation, defaulting to the Engine setting when `None`
+        self.drop_substeps = kwargs.pop("drop_substeps", None)
 
         if kwargs:
             raise Exception(f"Invalid Simulation option in {kwargs}")
+        if self.param_sets is not None and (not self.param_sets or not all(isinstance(param_set, dict) for param_set in self.param_sets)):
+            raise ValueError("Parameter sets must be a non-empty list of dicts")
+
+    @classmethod
+    def from_matrix(cls, model: Model, rows, timesteps=100, **kwargs):
+        """
+        Creates a Simulation from a configuration matrix, e.g. a list of dicts or a DataFrame, where each row is a parameter subset.
+        An optional `runs` column sets the number of runs of each subset, otherwise `runs` applies to all subsets,
+        and an optional `seed` column replaces the `seed` Engine option for the runs of each subset.
+        Each row must contain the parameters of the Model, or of the first row when the Model has no parameters.
+        """
+        if hasattr(rows, "to_dict"):
+            rows = rows.to_dict("records")
+        rows = [dict(row) for row in rows]
+        if not rows:
+            raise ValueError("Configuration matrix requires at least one row")
+
+        required_keys = set(model.params) or set(rows[0]) - {"runs", "seed"}
+        for index, row in enumerate(rows):
+            missing_keys = required_keys - set(row)
+            if missing_keys:
+                raise ValueError(f"Configuration matrix row {index} is missing parameters {sorted(missing_keys)}")
+
+        runs = kwargs.pop("runs", 1)
+        return cls(
+            model=model,
+            timesteps=timesteps,
+            runs=max(row.get("runs", runs) for row in rows),
+            # Parameters are ordered as in the Model, followed by any additional columns
+            param_matrix=[{"runs": runs, **{key: row[key] for key in model.params}, **row} for row in rows],
+            **kwargs
+        )
+
+    def run(self, only=None, parquet_path=None, timeout_seconds=None, plan_only=False, ndjson_path=None, max_memory_mb=None):
+        """
+        Runs the Simulation, optionally restricted to the `only` list of `(run, subset)` units, e.g. to retry the failed runs of a previous run.
+        When `parquet_path` is set, the results are also written to a Parquet file, which requires the pyarrow package.
+        When `timeout_seconds` is exceeded, no further runs are started, and runs in progress are interrupted at the next timestep,
+        returning the completed timesteps and setting `timed_out`.
+        When `plan_only` is set, nothing is executed, and a summary of the runs, timesteps, and an upper bound on the records is returned.
+        When `ndjson_path` is set, the results are written to a newline-delimited JSON file as the results of each run are collected,
+        rather than retained, and the number of states written is returned. Multi-process backends collect the results of every run
+        before they're written, so memory is only bounded when using the `SINGLE_PROCESS` backend.
+        When `max_memory_mb` is set, the memory of each process executing runs is checked every 10 timesteps against the soft
+        memory budget in MB, raising a `MemoryError`, as runs in progress can't be spilled. The memory is also checked as the results
+        of each run are collected, spilling buffered states to disk when using the `spill_threshold` Engine option, or otherwise raising.
+        """
+        return self.engine._run(executable=self, only=only, parquet_path=parquet_path, timeout_seconds=timeout_seconds, plan_only=plan_only, ndjson_path=ndjson_path, max_memory_mb=max_memory_mb)
+
+    def check_reproducible(self, ignore_keys=("wall_time",)):
+        """
+        Runs the Simulation twice to check the results are identical, e.g. to catch unseeded random number generators,
+        or state mutation. Returns `None` if the results are identical, otherwise the first differing state as a dict
+        of its `index` in the results, the `first` and `second` state, and the differing `keys`.
+        The results are compared before postprocessing, and the results of any previous run are left unchanged.
+
+        Args:
+            ignore_keys (Iterable): State keys expected to differ between runs. Defaults to `("wall_time",)`.
+        """
+        # Compares the results before postprocessing, and restores the results of the previous run
+        previous = self.results, self.exceptions
+        try:
+            self.run()
+            first = list(self.results)
+            self.run()
+            second = list(self.results)
+        finally:
+            self.results, self.exceptions = previous
+        for index in range(max(len(first), len(second))):
+            first_state = first[index] if index < len(first) else {}
+            second_state = second[index] if index < len(second) else {}
+            keys = sorted(
+                key for key in first_state.keys() | second_state.keys()
+                if key not in ignore_keys and (
+                    key not in first_state or key not in second_state or first_state[key] != second_state[key]
+                )
+            )
+            if keys:
+                return {"index": index, "first": first_state, "second": second_state, "keys": keys}
+        return None
+
+    def result_schema(self):
+        """
+        Returns the columns of the results, without running the Simulation, as an ordered dict of column name to type:
+        the State Variables, with types inferred from their initial values, followed by the bookkeeping keys
+        enabled by the Simulation and its Engine, or sorted alphabetically when using the `sort_columns` Engine option.
+        When using the `melt` Engine option, the melted State Variables are replaced by the `variable` and `value` columns.
+        When using the `record_signals` Engine option, the Policy Functions of each PSU are called once, against the initial state
+        and the first parameter subset, to infer the recorded signals.
+        Values that aren't a `bool`, `int`, `float`, or `str` are typed as `object`.
+        """
+        schema = {
+            key: type(value) if type(value) in [bool, int, float, str] else object
+            for (key, value) in self.model.initial_state.items()
+        }
+        for (key, aggregate) in (self.cumulative or {}).items():
+            schema[f"{key}_cum{aggregate}"] = schema[key]
+        for key in self.deltas or []:
+            schema[f"{key}_delta"] = schema[key]
+        schema.update({"simulation": int, "subset": int, "run": int})
+        if self.engine.run_uuid:
+            schema["run_uuid"] = str
+        schema["substep"] = int
+        if any("label" in psu for psu in self.model.state_update_blocks):
+            # The substep_label of the initial state, and unlabelled PSUs, is None
+            schema["substep_label"] = object
+        if any(psu.get("record_iterations", False) for psu in self.model.state_update_blocks):
+            # The iteration of the initial state is None
+            schema["iteration"] = object
+        schema["timestep"] = int
+        if self.record_wall_time:
+            schema["wall_time"] = float
+        if self.engine.record_updated:
+            schema["__updated__"] = list
+        for (key, value) in self.model.params.items():
+            if isinstance(value, Scenarios):
+                # Each state is labelled with the name of the scenario of each Scenarios parameter
+                schema[f"{key}_scenario"] = str
+        if self.engine.record_signals:
+            schema.update(self._signal_schema(schema))
+        if self.engine.sort_columns:
+            schema = dict(sorted(schema.items()))
+        if self.engine.melt:
+            melted = [schema.pop(key) for key in self.engine.melt]
+            schema["variable"] = str
+            # Melted values share a column, typed as int only when every melted State Variable is an int
+            schema["value"] = int if all(column_type is int for column_type in melted) else float
+        return schema
+
+    def _signal_schema(self, schema):
+        # The reduced signals aren't known until the Policy Functions return, so the Policy Functions of each PSU
+        # are called once, against the initial state and the first parameter subset
+        params = self.parameter_sweep()[0]
+        if self.run_params:
+            params = {**params, **select_run_params(self.run_params, 0)}
+        initial_state = {**copy.deepcopy(self.model.initial_state), "substep": 0, "timestep": 0}
+        state_update_blocks = list(map(_normalize_psu, self.model.state_update_blocks))
+        if self.engine.scratch:
+            state_update_blocks = _wrap_state_update_blocks(partial(_with_scratch, {}), state_update_blocks)
+        signal_schema = {}
+        for (substep, psu) in enumerate(state_update_blocks):
+            signals = reduce_signals(
+                _psu_params(params, psu), substep, [[initial_state]], initial_state, psu, active_policies=set(psu["policies"])
+            )
+            for (key, value) in signals.items():
+                if f"{SIGNAL_PREFIX}{key}" not in schema:
+                    signal_schema[f"{SIGNAL_PREFIX}{key}"] = type(value) if type(value) in [bool, int, float, str] else object
+        return signal_schema
+
+    def parameter_sweep(self):
+        """
+        Returns the parameter subsets of the Model, or of the compiled plan, generated on first access
+        and cached until the Model is reassigned or its params change. When set, the `param_sets` are returned directly.
+        """
+        if self.param_sets is not None:
+            return self.param_sets
+        if self.plan:
+            return self.plan.param_sweep
+        cache = self._param_sweep_cache
+        if not cache or cache[0] is not self.model or not _params_unchanged(cache[1], self.model.params) or cache[2] != self.sweep:
+            # The params are cached as a snapshot of their values, so that params mutated in place invalidate the cache
+            cache = self._param_sweep_cache = (
+                self.model, copy.deepcopy(self.model.params), self.sweep, generate_parameter_sweep(self.model.params, self.sweep)
+            )
+        return cache[3]
+
+    def compile(self):
+        """
+        Validates the Model and caches a copy of it along with the parameter sweep, which are reused by subsequent runs.
+        Call `compile()` again after changing the Model.
+        """
+        # The Model deep copies the initial state and params, while the state update blocks are copied down to the
+        # dicts of functions, so that changes to the Model, including in place, don't leak into the compiled plan
+        model = Model(
+            initial_state=self.model.initial_state,
+            state_update_blocks=[
+                {key: dict(value) if isinstance(value, dict) else value for (key, value) in psu.items()}
+                for psu in self.model.state_update_blocks
+            ],
+            params=self.model.params,
+        )
+        validate_state_update_blocks(model.initial_state, model.state_update_blocks)
+        self.plan = SimulationPlan(model=model, param_sweep=generate_parameter_sweep(model.params, self.sweep))
+        return self.plan
+
+    def stepper(self, run=0, subset=0):
+        """
+        Returns a `SimulationStepper` executing the zero-based `run` of parameter `subset` one timestep at a time,
+        e.g. to embed the Simulation in the loop of another engine.
+        """
+        return SimulationStepper(self, run, subset)
+
+
+class SimulationStepper:
+    """
+    Executes a single run of a Simulation one timestep at a time, in the calling process, for co-simulation
+    with another engine driving the clock. The run is suspended between calls to `step()`.
+    """
 
-    def run(self):
-        return self.engine._run(executable=self)
+    def __init__(self, simulation: Simulation, run: int=0, subset: int=0):
+        run_args = next(simulation.engine._run_stream(simulation, [Engine._get_config(simulation)], {(run, subset)}), None)
+        if run_args is None:
+            raise ValueError(f"Simulation has no run {run} of subset {subset}")
+        self._stream = iter_single_run(*tuple(run_args), step_timesteps=True)
+        self._timesteps = simulation.timesteps + (1 if simulation.initial_substep else 0)
+        self.timestep = 0
+        self.done = False
+        # The results and run metadata, once the run is done
+        self.results = None
+        self.metadata = None
+        # The initial records, e.g. any initial history and the initial state, are yielded before the first timestep
+        try:
+            state = next(self._stream)
+            while state is not TIMESTEP_END:
+                state = next(self._stream)
+        except StopIteration as stop:
+            # The run failed before its first timestep
+            self._finish(stop)
+        self.initial_state = run_args.initial_state
+
+    def _finish(self, stop: StopIteration):
+        results, exception, _, metadata = stop.value
+        self.done = True
+        self.results = [state for states in results for state in states]
+        self.metadata = metadata
+        if exception:
+            raise exception
+
+    def step(self) -> list:
+        """
+        Advances the run by exactly one timestep, returning the recorded substates of the timestep.
+        """
+        if self.done:
+            raise RuntimeError("Simulation run is done")
+        states = []
+        try:
+            state = next(self._stream)
+            while state is not TIMESTEP_END:
+                states.append(state)
+                state = next(self._stream)
+        except StopIteration as stop:
+            # The run ended early, e.g. when its terminating condition was met
+            self.timestep += 1 if states else 0
+            self._finish(stop)
+            return states
+        self.timestep += 1
+        if self.timestep == self._timesteps:
+            # The run is finished after its final timestep
+            try:
+                next(self._stream)
+            except StopIteration as stop:
+                self._finish(stop)
+        return states
 
 
 class Experiment(Executable):
@@ -163,11 +503,44 @@ class Experiment(Executable):
         self.simulations = []
         self.add_simulations(simulations)
 
+        # Parallelism strategy, overriding the Engine backend and number of processes when set
+        self.backend = kwargs.pop("backend", None)
+        self.workers = kwargs.pop("workers", None)
+        # Whether to check the state schema of each Simulation matches, or to fill missing keys with fill_value
+        self.reconcile_schema = kwargs.pop("reconcile_schema", None)
+        self.fill_value = kwargs.pop("fill_value", None)
+        if self.reconcile_schema not in [None, "raise", "fill"]:
+            raise ValueError(f"Schema reconciliation must be one of [None, 'raise', 'fill'], not {self.reconcile_schema}")
+        if self.backend is not None and self.backend not in EXPERIMENT_BACKENDS:
+            raise ValueError(f"Experiment backend must be one of {list(EXPERIMENT_BACKENDS)}, not {self.backend}")
+
         if kwargs:
             raise Exception(f"Invalid Experiment option in {kwargs}")
 
-    def run(self):
-        return self.engine._run(executable=self)
+    def run(self, only=None, parquet_path=None, timeout_seconds=None, plan_only=False, ndjson_path=None, max_memory_mb=None):
+        """
+        Runs the Experiment, optionally restricted to the `only` list of `(simulation, run, subset)` units, e.g. to retry the failed runs of a previous run.
+        When `parquet_path` is set, the results are also written to a Parquet file, which requires the pyarrow package.
+        When `timeout_seconds` is exceeded, no further runs are started, and runs in progress are interrupted at the next timestep,
+        returning the completed timesteps and setting `timed_out`.
+        When `plan_only` is set, nothing is executed, and a summary of the runs, timesteps, and an upper bound on the records is returned.
+        When `ndjson_path` is set, the results are written to a newline-delimited JSON file as the results of each run are collected,
+        rather than retained, and the number of states written is returned. Multi-process backends collect the results of every run
+        before they're written, so memory is only bounded when using the `SINGLE_PROCESS` backend.
+        When `max_memory_mb` is set, the memory of each process executing runs is checked every 10 timesteps against the soft
+        memory budget in MB, raising a `MemoryError`, as runs in progress can't be spilled. The memory is also checked as the results
+        of each run are collected, spilling buffered states to disk when using the `spill_threshold` Engine option, or otherwise raising.
+        """
+        # The parallelism strategy only overrides the Engine for this run, as the Engine may be shared
+        backend, processes = self.engine.backend, self.engine.processes
+        try:
+            if self.backend is not None:
+                self.engine.backend = EXPERIMENT_BACKENDS[self.backend]
+            if self.workers is not None:
+                self.engine.processes = self.workers
+            return self.engine._run(executable=self, only=only, parquet_path=parquet_path, timeout_seconds=timeout_seconds, plan_only=plan_only, ndjson_path=ndjson_path, max_memory_mb=max_memory_mb)
+        finally:
+            self.engine.backend, self.engine.processes = backend, processes
 
     def add_simulations(self, simulations):
         if not isinstance(simulations, list):
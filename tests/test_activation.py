@@ -0,0 +1,69 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_event(params, substep, state_history, previous_state):
+    return {'events': 1}
+
+
+def policy_always(params, substep, state_history, previous_state):
+    return {'events': 0}
+
+
+def update_events(params, substep, state_history, previous_state, policy_input):
+    return 'events', previous_state['events'] + policy_input.get('events', 0)
+
+
+def run_simulation(activation, seed=1):
+    model = Model(
+        initial_state={'events': 0},
+        state_update_blocks=[{
+            'policies': {'event': policy_event, 'always': policy_always},
+            'variables': {'events': update_events},
+            'activation': activation,
+        }],
+    )
+    simulation = Simulation(model=model, timesteps=1000, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, seed=seed)
+    return simulation.run()
+
+
+def test_activation():
+    result = run_simulation({'event': 0.25})
+    assert 150 < result[-1]['events'] < 350
+    assert run_simulation({'event': 0.25}) == result
+    assert run_simulation({'event': 0.25}, seed=2) != result
+
+    assert run_simulation({'event': 0})[-1]['events'] == 0
+    assert run_simulation({'event': 1})[-1]['events'] == 1000
+
+
+def test_activation_unknown_policy():
+    with pytest.raises(KeyError):
+        run_simulation({'unknown': 0.5})
+
+
+def test_activation_drawn_once_per_substep():
+    # With sequential variables, signals are reduced once per State Update Function, using the same active policies
+    model = Model(
+        initial_state={'events': 0, 'other_events': 0},
+        state_update_blocks=[{
+            'policies': {'event': policy_event},
+            'variables': {
+                'events': update_events,
+                'other_events': lambda params, substep, state_history, previous_state, policy_input: (
+                    'other_events', previous_state['other_events'] + policy_input.get('events', 0)
+                ),
+            },
+            'activation': {'event': 0.5},
+            'sequential_variables': True,
+        }],
+    )
+    simulation = Simulation(model=model, timesteps=100, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, seed=1)
+    result = simulation.run()
+
+    assert 0 < result[-1]['events'] < 100
+    assert all(state['events'] == state['other_events'] for state in result)
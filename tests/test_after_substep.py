@@ -0,0 +1,33 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.core import SubstepHookError
+from tests.test_cases import basic
+
+import pytest
+
+
+def test_after_substep():
+    substeps = []
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(
+        model=model, timesteps=2, runs=1,
+        after_substep=lambda state: substeps.append((state['timestep'], state['substep'])),
+    )
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+
+    assert substeps == [(1, 1), (1, 2), (2, 1), (2, 2)]
+
+def assert_conservation(state):
+    assert state['b'] < 10, 'b exceeded 10'
+
+def test_after_substep_exception():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=5, runs=1, after_substep=assert_conservation)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(SubstepHookError) as e:
+        simulation.run()
+    assert (e.value.timestep, e.value.substep) == (2, 2)
+    assert str(e.value) == "after_substep hook failed at simulation 0 / run 1 / subset 0 / timestep 2 / substep 2"
+    assert str(e.value.__cause__) == 'b exceeded 10'
@@ -0,0 +1,50 @@
+import copy
+import pickle
+import pytest
+
+from radcad import Model, Simulation
+from radcad.core import MISSING
+from radcad.engine import Engine, Backend
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+
+def update_b(params, substep, state_history, previous_state, policy_input):
+    return 'b', previous_state['a']
+
+
+state_update_blocks = [
+    {'policies': {}, 'variables': {'a': update_a}},
+    {'policies': {}, 'variables': {'b': update_b}},
+]
+
+
+def test_allow_new_keys():
+    model = Model(initial_state={'a': 0}, state_update_blocks=state_update_blocks)
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, allow_new_keys=True)
+    result = simulation.run()
+
+    assert [(state['timestep'], state['substep'], state['a'], state['b']) for state in result] == [
+        (0, 0, 0, MISSING),
+        (1, 1, 1, MISSING),
+        (1, 2, 1, 1),
+        (2, 1, 2, 1),
+        (2, 2, 2, 2),
+    ]
+
+
+def test_new_keys_rejected_by_default():
+    model = Model(initial_state={'a': 0}, state_update_blocks=state_update_blocks)
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(KeyError):
+        simulation.run()
+
+
+def test_missing_sentinel_copy():
+    assert copy.deepcopy(MISSING) is MISSING
+    assert pickle.loads(pickle.dumps(MISSING)) is MISSING
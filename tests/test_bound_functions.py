@@ -0,0 +1,59 @@
+from unittest import mock
+
+from radcad import Model, Simulation
+from radcad.core import reduce_signals, _bind_functions
+from radcad.engine import Engine, Backend
+
+
+def policy_scaled(params, substep, state_history, previous_state, scale=1):
+    return {'delta': scale}
+
+def update_clamped(params, substep, state_history, previous_state, policy_input, key, upper=None):
+    value = previous_state[key] + policy_input['delta']
+    return key, min(value, upper) if upper is not None else value
+
+def test_bound_functions():
+    model = Model(
+        initial_state={'a': 0, 'b': 0},
+        state_update_blocks=[{
+            'policies': {'p': (policy_scaled, {'scale': 2})},
+            'variables': {
+                'a': (update_clamped, {'key': 'a', 'upper': 5}),
+                'b': (update_clamped, {'key': 'b'}),
+            },
+        }],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=4, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert [(state['a'], state['b']) for state in result] == [(0, 0), (2, 2), (4, 4), (5, 6), (5, 8)]
+
+def test_bound_functions_once_per_run():
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{
+            'policies': {'p': (policy_scaled, {'scale': 2})},
+            'variables': {'a': (update_clamped, {'key': 'a'})},
+        }],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with mock.patch('radcad.core._bind_functions', wraps=_bind_functions) as bind_functions:
+        result = simulation.run()
+    assert result[-1]['a'] == 20
+    # The policies and variables of each PSU are bound once per run, rather than every substep
+    assert bind_functions.call_count == 2 * 2
+
+def test_reduce_signals_bound_functions():
+    psu = {
+        'policies': {
+            '1': (policy_scaled, {'scale': 2}),
+            '2': policy_scaled,
+        },
+        'variables': {},
+    }
+    assert reduce_signals({}, 1, [], {}, psu) == {'delta': 3}
@@ -0,0 +1,49 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def run(cacheable, sequential_variables=False):
+    calls = []
+
+    def policy(params, substep, state_history, previous_state):
+        calls.append(previous_state['timestep'])
+        return {'step': 1}
+
+    def update_x(params, substep, state_history, previous_state, policy_input):
+        return 'x', previous_state['x'] + policy_input['step']
+
+    def update_y(params, substep, state_history, previous_state, policy_input):
+        return 'y', previous_state['y'] + policy_input['step']
+
+    state_update_blocks = [
+        {
+            'policies': {'policy': policy},
+            'variables': {'x': update_x, 'y': update_y},
+            # Re-executed three times each timestep
+            'converged': lambda previous_substate, substate: substate['x'] % 3 == 0,
+            'sequential_variables': sequential_variables,
+            'cacheable': cacheable,
+        },
+    ]
+    model = Model(initial_state={'x': 0, 'y': 0}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation.run(), calls
+
+
+def test_cacheable():
+    result, calls = run(cacheable=True)
+    # Policies are evaluated once per timestep, and the cache is invalidated each timestep
+    assert calls == [0, 1]
+    assert [(state['x'], state['y']) for state in result] == [(0, 0), (3, 3), (6, 6)]
+
+
+def test_cacheable_sequential_variables():
+    result, calls = run(cacheable=True, sequential_variables=True)
+    assert calls == [0, 1]
+
+
+def test_not_cacheable():
+    result, calls = run(cacheable=False, sequential_variables=True)
+    assert len(calls) == 12
+    assert [(state['x'], state['y']) for state in result] == [(0, 0), (3, 3), (6, 6)]
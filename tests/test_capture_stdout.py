@@ -0,0 +1,47 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+
+
+def policy(params, substep, state_history, previous_state):
+    print(f"timestep {previous_state['timestep'] + 1}, a = {params['a']}")
+    return {}
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    if previous_state['timestep'] == params['fail_at']:
+        raise Exception('Forced exception from state update function')
+    return 'a', previous_state['a']
+
+def model(fail_at=None):
+    return Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {'p': policy}, 'variables': {'a': update_a}}],
+        params={'a': [1, 2], 'fail_at': fail_at},
+    )
+
+def test_capture_stdout(capsys):
+    simulation = Simulation(model=model(), timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, capture_stdout=True)
+    simulation.run()
+
+    assert capsys.readouterr().out == ''
+    assert [run['stdout'] for run in simulation.exceptions] == [
+        'timestep 1, a = 1\ntimestep 2, a = 1\n',
+        'timestep 1, a = 2\ntimestep 2, a = 2\n',
+    ]
+
+def test_capture_stdout_exception():
+    simulation = Simulation(model=model(fail_at=1), timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, capture_stdout=True, raise_exceptions=False)
+    simulation.run()
+
+    assert simulation.exceptions[0]['stdout'] == 'timestep 1, a = 1\ntimestep 2, a = 1\n'
+
+def test_capture_stdout_disabled(capsys):
+    simulation = Simulation(model=model(), timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+
+    assert 'timestep 1, a = 1' in capsys.readouterr().out
+    assert 'stdout' not in simulation.exceptions[0]
@@ -0,0 +1,80 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+import logging
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] * 1e200
+
+initial_state = {
+    'a': 1.0,
+    'b': True,
+}
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {
+            'a': update_a,
+        }
+    },
+]
+
+def test_check_finite_raise():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, check_finite=True)
+
+    with pytest.raises(ValueError) as e:
+        simulation.run()
+    assert str(e.value) == "Non-finite value inf for state variable a at timestep 2 / substep 1"
+
+def test_check_finite_warn(caplog):
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, check_finite="warn")
+
+    with caplog.at_level(logging.WARNING):
+        result = simulation.run()
+    assert len(result) == 11
+    assert "Non-finite value inf for state variable a at timestep 2 / substep 1" in caplog.text
+
+def test_check_finite_disabled():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert simulation.run()[-1]['a'] == float('inf')
+
+def policy_ratio(params, substep, state_history, previous_state):
+    return {'ratio': previous_state['a'] / (3 - previous_state['timestep']) if previous_state['timestep'] != 3 else float('nan')}
+
+def update_ratio(params, substep, state_history, previous_state, policy_input):
+    return 'a', policy_input['ratio']
+
+signal_state_update_blocks = [
+    {
+        'policies': {'ratio': policy_ratio},
+        'variables': {
+            'a': update_ratio,
+        }
+    },
+]
+
+def test_check_signal_finite():
+    model = Model(initial_state=initial_state, state_update_blocks=signal_state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, check_signal_finite=True)
+
+    with pytest.raises(ValueError) as e:
+        simulation.run()
+    assert str(e.value) == "Non-finite value nan for policy signal ratio at timestep 4 / substep 1"
+
+def test_check_signal_finite_disabled():
+    model = Model(initial_state=initial_state, state_update_blocks=signal_state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert len(simulation.run()) == 11
@@ -0,0 +1,47 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import random
+
+
+def test_check_reproducible():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1, record_wall_time=True)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert simulation.check_reproducible() is None
+
+def test_check_reproducible_divergence():
+    calls = []
+
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        calls.append(1)
+        # Diverges at timestep 3 of the second run
+        return 'a', previous_state['a'] + (random.random() if len(calls) == 8 else 1)
+
+    model = Model(
+        initial_state={'a': 0, 'b': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=5, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    divergence = simulation.check_reproducible()
+
+    assert divergence['index'] == 3
+    assert divergence['keys'] == ['a']
+    assert divergence['first']['timestep'] == divergence['second']['timestep'] == 3
+
+def test_check_reproducible_postprocess():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    # The postprocessed results aren't a list of states
+    simulation.postprocess = lambda results: {'final': results[-1]}
+    previous = simulation.run()
+    previous_results = simulation.results
+
+    assert simulation.check_reproducible() is None
+    # The results of the previous run are left unchanged
+    assert simulation.results is previous_results
+    assert previous == {'final': previous_results[-1]}
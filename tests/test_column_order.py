@@ -0,0 +1,47 @@
+from collections import OrderedDict
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+def update_c(params, substep, state_history, previous_state, policy_input):
+    return 'c', previous_state['c'] + 1
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'a': update_a, 'c': update_c},
+    },
+]
+
+
+def run(initial_state, **kwargs):
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation, simulation.run()
+
+
+def test_initial_state_column_order():
+    # Columns are ordered as the initial state, followed by the bookkeeping keys
+    for initial_state in [{'c': 0, 'b': 0, 'a': 0}, OrderedDict([('c', 0), ('b', 0), ('a', 0)])]:
+        simulation, result = run(initial_state)
+        for state in result:
+            assert list(state) == ['c', 'b', 'a', 'simulation', 'subset', 'run', 'substep', 'timestep']
+        assert list(simulation.result_schema()) == list(result[0])
+
+
+def test_sort_columns():
+    simulation, result = run({'c': 0, 'b': 0, 'a': 0}, sort_columns=True)
+    for state in result:
+        assert list(state) == sorted(state)
+    assert list(simulation.result_schema()) == list(result[0])
+    assert result[-1]['a'] == 2
+
+
+def test_sort_columns_spilled():
+    simulation, result = run({'c': 0, 'b': 0, 'a': 0}, sort_columns=True, spill_threshold=1)
+    assert [list(state) for state in result] == [sorted(state) for state in result]
@@ -0,0 +1,68 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.wrappers import SimulationPlan
+from tests.test_cases import basic
+
+import pytest
+
+
+def test_compile():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    plan = simulation.compile()
+    assert isinstance(plan, SimulationPlan)
+    assert plan.param_sweep == [{'a': 1, 'b': 1}, {'a': 2, 'b': 1}]
+
+    assert simulation.run() == result
+    assert simulation.run() == result
+
+    # The compiled plan is reused until the Simulation is compiled again
+    simulation.model.params = {'a': [3], 'b': [1]}
+    assert simulation.run() == result
+    simulation.compile()
+    assert simulation.run() != result
+
+def test_compile_in_place_changes():
+    state_update_blocks = [dict(psu, variables=dict(psu['variables'])) for psu in basic.state_update_blocks]
+    model = Model(initial_state=basic.states, state_update_blocks=state_update_blocks, params={'a': [1, 2], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.compile()
+    result = simulation.run()
+
+    # Changes to the Model in place don't leak into the compiled plan
+    simulation.model.params['a'][0] = 3
+    simulation.model.state_update_blocks[0]['variables'].pop('a')
+    assert simulation.run() == result
+
+def test_compile_invalid_state_update_blocks():
+    state_update_blocks = [
+        {
+            'policies': {},
+            'variables': {
+                'c': basic.update_a
+            }
+        },
+    ]
+    model = Model(initial_state=basic.states, state_update_blocks=state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+
+    with pytest.raises(KeyError):
+        simulation.compile()
+
+    state_update_blocks = [
+        {
+            'policies': {
+                'p': 'not a function',
+            },
+            'variables': {}
+        },
+    ]
+    model = Model(initial_state=basic.states, state_update_blocks=state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+
+    with pytest.raises(TypeError):
+        simulation.compile()
@@ -0,0 +1,64 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_newton_step(params, substep, state_history, previous_state):
+    x = previous_state['x']
+    return {'step': (x * x - params['target']) / (2 * x)}
+
+def update_x(params, substep, state_history, previous_state, policy_input):
+    return 'x', previous_state['x'] - policy_input['step']
+
+def converged(previous_substate, substate):
+    return abs(substate['x'] - previous_substate['x']) < 1e-12
+
+initial_state = {
+    'x': 1.0
+}
+
+def state_update_blocks(**options):
+    return [
+        {
+            'policies': {
+                'newton_step': policy_newton_step,
+            },
+            'variables': {
+                'x': update_x,
+            },
+            'converged': converged,
+            **options,
+        },
+    ]
+
+def test_converged():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks(), params={'target': [2.0]})
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = simulation.run()
+    assert len(result) == 2
+    assert abs(result[-1]['x'] - 2 ** 0.5) < 1e-12
+
+def test_record_iterations():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks(record_iterations=True), params={'target': [2.0]})
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = simulation.run()
+    trace = [state['x'] for state in result[1:]]
+    assert len(trace) > 2
+    assert trace[:2] == [1.5, 1.5 - (1.5 ** 2 - 2) / 3]
+    assert all(state['substep'] == 1 and state['timestep'] == 1 for state in result[1:])
+    # Records of the same substep are distinguished by their iteration
+    assert result[0]['iteration'] is None
+    assert [state['iteration'] for state in result[1:]] == list(range(1, len(trace) + 1))
+    assert list(simulation.result_schema()) == list(result[0])
+    assert simulation.result_schema()['iteration'] is object
+
+def test_max_iterations():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks(max_iterations=2, record_iterations=True), params={'target': [2.0]})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = simulation.run()
+    assert len(result) == 1 + 2 * 2
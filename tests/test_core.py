@@ -31,6 +31,59 @@ def test_generate_parameter_sweep():
     param_sweep = generate_parameter_sweep(params)
     assert param_sweep == [{'a': 0, 'b': 0, 'c': 0}, {'a': 1, 'b': 1, 'c': 0}, {'a': 2, 'b': 1, 'c': 0}]
 
+def test_generate_parameter_sweep_padding_warning():
+    params = {
+        'a': [0, 1, 2],
+        'b': [0, 1],
+        'c': [0]
+    }
+    with pytest.warns(UserWarning, match="Parameter b has 2 values, less than the 3 parameter subsets"):
+        generate_parameter_sweep(params)
+
+def test_generate_parameter_sweep_single_param_set():
+    assert generate_parameter_sweep({}) == [{}]
+
+    params = {
+        'a': 1,
+        'b': 'label',
+        'c': {'x': 1},
+    }
+    assert generate_parameter_sweep(params) == [{'a': 1, 'b': 'label', 'c': {'x': 1}}]
+
+    params = {
+        'a': [0, 1],
+        'b': 'label',
+    }
+    assert generate_parameter_sweep(params) == [{'a': 0, 'b': 'label'}, {'a': 1, 'b': 'label'}]
+
+def test_generate_parameter_sweep_dedup():
+    params = {'a': [1, 2, 1, 3, 3], 'b': [{'c': [1]}], 'd': 0}
+    assert generate_parameter_sweep(params) == [
+        {'a': 1, 'b': {'c': [1]}, 'd': 0},
+        {'a': 2, 'b': {'c': [1]}, 'd': 0},
+        {'a': 1, 'b': {'c': [1]}, 'd': 0},
+        {'a': 3, 'b': {'c': [1]}, 'd': 0},
+        {'a': 3, 'b': {'c': [1]}, 'd': 0},
+    ]
+    assert generate_parameter_sweep(params, dedup_sweep=True) == [
+        {'a': 1, 'b': {'c': [1]}, 'd': 0},
+        {'a': 2, 'b': {'c': [1]}, 'd': 0},
+        {'a': 3, 'b': {'c': [1]}, 'd': 0},
+    ]
+
+def test_generate_parameter_sweep_key_order():
+    params = {'c': [1, 2], 'a': 1, 'b': [3]}
+    param_sweep = generate_parameter_sweep(params)
+    assert [list(param_set) for param_set in param_sweep] == [['c', 'a', 'b'], ['c', 'a', 'b']]
+
+def test_generate_parameter_sweep_invalid_params():
+    with pytest.raises(TypeError, match="Parameter a of type generator can't be indexed"):
+        generate_parameter_sweep({'a': (x for x in [1, 2])})
+    with pytest.raises(TypeError, match="Parameter b of type set can't be indexed"):
+        generate_parameter_sweep({'a': [1], 'b': {1, 2}})
+    with pytest.raises(ValueError, match="Parameter a of type list has no values"):
+        generate_parameter_sweep({'a': []})
+
 def test_reduce_signals():
     psu = {
         'policies': {
@@ -48,6 +101,73 @@ def test_reduce_signals():
     assert signals['signal_c'] == 100e52
     assert signals['signal_d'] == 3.3333000000000003e-10
 
+def test_reduce_signals_custom_reducer():
+    psu = {
+        'policies': {
+            '1': lambda params, substep, state_history, previous_state: {'signal_a': 2.0, 'signal_b': 1.0},
+            '2': lambda params, substep, state_history, previous_state: {'signal_a': 3.0},
+            '3': lambda params, substep, state_history, previous_state: {'signal_a': 4.0},
+        },
+        'variables': {},
+        'reducer': lambda params, substep, accumulator, value: accumulator * value * params['weight'],
+    }
+
+    signals = reduce_signals({'weight': 2}, 1, [], {}, psu)
+    assert signals['signal_a'] == 96.0
+    assert signals['signal_b'] == 1.0
+
+def test_reduce_signals_accumulator_init():
+    psu = {
+        'policies': {
+            '1': lambda params, substep, state_history, previous_state: {'signal_a': 2.0, 'signal_b': 3.0},
+            '2': lambda params, substep, state_history, previous_state: {'signal_a': 3.0},
+        },
+        'variables': {},
+        'reducer': lambda params, substep, accumulator, value: accumulator * value + 1,
+        'accumulator_init': 1,
+    }
+
+    assert reduce_signals({}, 1, [], {}, psu) == {'signal_a': 10.0, 'signal_b': 4.0}
+
+    psu['reducer'] = lambda params, substep, accumulator, value: accumulator + [value]
+    psu['accumulator_init'] = []
+    assert reduce_signals({}, 1, [], {}, psu) == {'signal_a': [2.0, 3.0], 'signal_b': [3.0]}
+
+def test_reduce_signals_deep_merge():
+    signal = {'deltas': {'a': 1, 'b': {'c': 1.5}}, 'label': 'x'}
+    psu = {
+        'policies': {
+            '1': lambda params, substep, state_history, previous_state: signal,
+            '2': lambda params, substep, state_history, previous_state: {'deltas': {'a': 2, 'b': {'d': 1}}, 'label': 'y'},
+            '3': lambda params, substep, state_history, previous_state: {'deltas': {'b': {'c': 0.5}}},
+        },
+        'variables': {},
+        'reducer': 'deep_merge',
+    }
+
+    signals = reduce_signals({}, 1, [], {}, psu)
+    assert signals == {'deltas': {'a': 3, 'b': {'c': 2.0, 'd': 1}}, 'label': 'y'}
+    assert signal == {'deltas': {'a': 1, 'b': {'c': 1.5}}, 'label': 'x'}
+
+    psu['reducer'] = 'product'
+    with pytest.raises(ValueError, match="Reducer must be a function or one of"):
+        reduce_signals({}, 1, [], {}, psu)
+
+def test_reduce_signals_list():
+    psu = {
+        'policies': {
+            '1': lambda params, substep, state_history, previous_state: [{'signal_a': 1.0}, {'signal_a': 2.0, 'signal_b': 1.0}],
+            '2': lambda params, substep, state_history, previous_state: {'signal_a': 3.0},
+        },
+        'variables': {}
+    }
+
+    signals = reduce_signals({}, 1, [], {}, psu)
+    assert signals == {'signal_a': 6.0, 'signal_b': 1.0}
+
+    psu['policies'] = {'1': psu['policies']['1']}
+    assert reduce_signals({}, 1, [], {}, psu) == {'signal_a': 3.0, 'signal_b': 1.0}
+
 @pytest.mark.skip(reason="deprecated API")
 def test_run():
     states = basic.states
@@ -0,0 +1,77 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_fees(params, substep, state_history, previous_state, policy_input):
+    return 'fees', [1.0, 3.0, 2.0][previous_state['timestep'] % 3]
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'fees': update_fees},
+    },
+]
+
+
+def run(cumulative, **kwargs):
+    model = Model(initial_state={'fees': 0.5}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=4, runs=1, cumulative=cumulative)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation, simulation.run()
+
+
+def test_cumulative():
+    simulation, result = run({'fees': 'sum'})
+    assert [state['fees'] for state in result] == [0.5, 1.0, 3.0, 2.0, 1.0]
+    # The first record is seeded with the initial value
+    assert [state['fees_cumsum'] for state in result] == [0.5, 1.5, 4.5, 6.5, 7.5]
+    assert list(simulation.result_schema())[:2] == ['fees', 'fees_cumsum']
+    assert list(simulation.result_schema()) == list(result[0])
+
+
+def test_cumulative_max_min():
+    _, result = run({'fees': 'max'})
+    assert [state['fees_cummax'] for state in result] == [0.5, 1.0, 3.0, 3.0, 3.0]
+    _, result = run({'fees': 'min'})
+    assert [state['fees_cummin'] for state in result] == [0.5] * 5
+
+
+def test_cumulative_drop_substeps():
+    _, result = run({'fees': 'max'}, drop_substeps=True)
+    assert result[-1]['fees_cummax'] == 3.0
+
+
+def set_fees(params, substep, state_history, previous_state, policy_input):
+    return 'fees', 1.0
+
+def update_volume(params, substep, state_history, previous_state, policy_input):
+    return 'volume', previous_state['volume'] + 1
+
+
+def test_cumulative_multiple_substeps():
+    model = Model(
+        initial_state={'fees': 0.0, 'volume': 0},
+        state_update_blocks=[
+            {'policies': {}, 'variables': {'fees': set_fees}},
+            {'policies': {}, 'variables': {'volume': update_volume}},
+        ],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=3, runs=1, cumulative={'fees': 'sum'})
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+    # Aggregated once per timestep, on the final substate
+    assert [state['fees_cumsum'] for state in result] == [0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0]
+
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, drop_substeps=True)
+    result = simulation.run()
+    assert [state['fees_cumsum'] for state in result] == [0.0, 1.0, 2.0, 3.0]
+
+
+def test_invalid_cumulative():
+    with pytest.raises(ValueError):
+        run({'fees': 'mean'})
+    with pytest.raises(KeyError):
+        run({'volume': 'sum'})
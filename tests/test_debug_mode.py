@@ -0,0 +1,30 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import os
+
+
+def update_pid(params, substep, state_history, previous_state, policy_input):
+    return 'pid', os.getpid()
+
+def test_debug_mode():
+    model = Model(
+        initial_state={'pid': None},
+        state_update_blocks=[{'policies': {}, 'variables': {'pid': update_pid}}],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=2)
+    simulation.engine = Engine(backend=Backend.MULTIPROCESSING, processes=2, debug_mode=True)
+    result = simulation.run()
+
+    assert all(state['pid'] == os.getpid() for state in result if state['timestep'] > 0)
+
+def test_debug_mode_result():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    simulation.engine = Engine(backend=Backend.PATHOS)
+    result = simulation.run()
+
+    simulation.engine = Engine(backend=Backend.PATHOS, debug_mode=True)
+    assert simulation.run() == result
@@ -0,0 +1,54 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+observed_tables = []
+
+def policy_observe(params, substep, state_history, previous_state):
+    observed_tables.append(previous_state['table'])
+    return {}
+
+def update_agents(params, substep, state_history, previous_state, policy_input):
+    # Mutates the state passed to it, which is safe for deep copied State Variables
+    previous_state['agents'].append(len(previous_state['agents']))
+    return 'agents', previous_state['agents']
+
+def update_count(params, substep, state_history, previous_state, policy_input):
+    return 'count', previous_state['count'] + 1
+
+state_update_blocks = [
+    {
+        'policies': {'observe': policy_observe},
+        'variables': {'agents': update_agents, 'count': update_count},
+    },
+]
+
+
+def run(deepcopy_keys):
+    observed_tables.clear()
+    initial_state = {'agents': [], 'count': 0, 'table': {'lookup': list(range(1000))}}
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=3, runs=1, deepcopy_keys=deepcopy_keys)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation.run()
+
+
+def test_deepcopy_keys():
+    result = run(['agents'])
+    # Deep copied State Variables are isolated between substeps
+    assert [state['agents'] for state in result] == [[], [0], [0, 1], [0, 1, 2]]
+    # Other State Variables are shallow references, not copied each substep
+    assert all(table is observed_tables[0] for table in observed_tables)
+
+
+def test_deepcopy_all_keys():
+    result = run(None)
+    assert [state['agents'] for state in result] == [[], [0], [0, 1], [0, 1, 2]]
+    assert observed_tables[1] is not observed_tables[0]
+
+
+def test_invalid_deepcopy_keys():
+    with pytest.raises(KeyError):
+        run(['unknown'])
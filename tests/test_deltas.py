@@ -0,0 +1,50 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_price(params, substep, state_history, previous_state, policy_input):
+    return 'price', previous_state['price'] * 2
+
+def update_supply(params, substep, state_history, previous_state, policy_input):
+    return 'supply', previous_state['supply'] - 1
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'price': update_price},
+    },
+    {
+        'policies': {},
+        'variables': {'supply': update_supply},
+    },
+]
+
+
+def run(deltas, **kwargs):
+    model = Model(initial_state={'price': 1.0, 'supply': 10}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=3, runs=1, deltas=deltas)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation, simulation.run()
+
+
+def test_deltas():
+    simulation, result = run(['price', 'supply'])
+    # The first record has no previous timestep
+    assert result[0]['price_delta'] is None
+    # Substates record the change since the final state of the previous timestep
+    assert [(state['price_delta'], state['supply_delta']) for state in result[1:]] == [
+        (1.0, 0), (1.0, -1), (2.0, 0), (2.0, -1), (4.0, 0), (4.0, -1)
+    ]
+    assert list(simulation.result_schema()) == list(result[0])
+
+
+def test_deltas_drop_substeps():
+    _, result = run(['supply'], drop_substeps=True)
+    assert [state['supply_delta'] for state in result] == [None, -1, -1, -1]
+
+
+def test_invalid_deltas():
+    with pytest.raises(KeyError):
+        run(['volume'])
@@ -25,3 +25,28 @@ def test_run():
     simulation_result = simulation_result.loc[keep]
     
     assert simulation_result.reset_index(drop=True).equals(drop_substeps_result.reset_index(drop=True))
+
+def test_simulation_drop_substeps():
+    states = basic.states
+    state_update_blocks = basic.state_update_blocks
+    params = basic.params
+    TIMESTEPS = 10
+    RUNS = 1
+
+    model = Model(initial_state=states, state_update_blocks=state_update_blocks, params=params)
+    simulation = Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS)
+    simulation_drop_substeps = Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS, drop_substeps=True)
+    experiment = Experiment(simulations=[simulation, simulation_drop_substeps])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = experiment.run()
+    substeps = [state['substep'] for state in result if state['simulation'] == 0 and state['subset'] == 0]
+    drop_substeps = [state['substep'] for state in result if state['simulation'] == 1 and state['subset'] == 0]
+
+    assert substeps == [0] + [1, 2] * TIMESTEPS
+    assert drop_substeps == [0] + [2] * TIMESTEPS
+
+    # The Simulation option overrides the Engine option
+    simulation_keep_substeps = Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS, drop_substeps=False)
+    simulation_keep_substeps.engine = Engine(backend=Backend.SINGLE_PROCESS, drop_substeps=True)
+    assert len(simulation_keep_substeps.run()) == (1 + 2 * TIMESTEPS) * len(params['a'])
@@ -0,0 +1,50 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_x(params, substep, state_history, previous_state, policy_input):
+    return 'x', 1
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'x': update_x},
+    },
+    {
+        'policies': {},
+        'variables': {'x': update_x},
+    },
+]
+
+
+def run(**kwargs):
+    model = Model(initial_state={'x': 0}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation.run()
+
+
+def test_duplicate_updates_last():
+    assert [state['x'] for state in run()] == [0, 1, 1, 1, 1]
+
+
+def test_duplicate_updates_warn():
+    with pytest.warns(UserWarning, match="State Variable x updated by substeps 1 and 2 of timestep 1"):
+        result = run(duplicate_updates="warn")
+    assert [state['x'] for state in result] == [0, 1, 1, 1, 1]
+
+
+def test_duplicate_updates_error():
+    with pytest.raises(ValueError, match="State Variable x updated by substeps 1 and 2 of timestep 1"):
+        run(duplicate_updates="error")
+
+
+def test_duplicate_updates_sum():
+    assert [state['x'] for state in run(duplicate_updates="sum")] == [0, 1, 2, 1, 2]
+
+
+def test_invalid_duplicate_updates():
+    with pytest.raises(ValueError):
+        Engine(duplicate_updates="first")
@@ -165,3 +165,56 @@ def test_raise_exceptions_false():
     print(exceptions)
     assert any([True if isinstance(exception['exception'], Exception) else False for exception in exceptions])
     assert isinstance(results, list)
+
+
+def test_retry_failed_runs():
+    fail = {'enabled': True}
+
+    def update_state_a(params, substep, state_history, previous_state, policy_input):
+        if fail['enabled'] and params['a'] == 2:
+            raise Exception('Forced exception for subset')
+        return 'state_a', previous_state['state_a'] + params['a']
+
+    model = Model(
+        initial_state={'state_a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'state_a': update_state_a}}],
+        params={'a': [1, 2, 3]},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, raise_exceptions=False)
+    simulation.run()
+
+    failed = [(e['run'], e['subset']) for e in simulation.exceptions if e['exception']]
+    assert failed == [(0, 1), (1, 1)]
+
+    fail['enabled'] = False
+    result = simulation.run(only=failed)
+    assert {(state['run'], state['subset']) for state in result} == {(1, 1), (2, 1)}
+    assert [state['state_a'] for state in result if state['run'] == 1] == [0, 2, 4]
+
+
+def test_experiment_only():
+    model = Model(
+        initial_state={'state_a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'state_a': lambda params, substep, state_history, previous_state, policy_input: ('state_a', 1)}}],
+        params={'a': [1, 2]},
+    )
+    experiment = Experiment([Simulation(model=model, timesteps=1, runs=1), Simulation(model=model, timesteps=1, runs=1)])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = experiment.run(only=[(1, 0, 1)])
+    assert {(state['simulation'], state['subset']) for state in result} == {(1, 1)}
+
+
+def test_only_no_runs():
+    model = Model(
+        initial_state={'state_a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'state_a': lambda params, substep, state_history, previous_state, policy_input: ('state_a', 1)}}],
+        params={'a': [1, 2]},
+    )
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert simulation.run(only=[(5, 5)]) == []
+    assert simulation.exceptions == []
+    assert not simulation.timed_out
@@ -0,0 +1,38 @@
+import pytest
+import warnings
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_delta(params, substep, state_history, previous_state):
+    return {'delta': 1, 'fee': 0}
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + policy_input['delta']
+
+
+def run_simulation(expected_signals):
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{
+            'policies': {'p': policy_delta},
+            'variables': {'a': update_a},
+            'expected_signals': expected_signals,
+        }],
+    )
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation.run()
+
+
+def test_expected_signals():
+    with warnings.catch_warnings():
+        warnings.simplefilter('error')
+        run_simulation(['delta', 'fee'])
+
+
+def test_unexpected_signals():
+    with pytest.warns(UserWarning, match=r"missing \['price'\], unexpected \['fee'\]"):
+        run_simulation(['delta', 'price'])
@@ -1,6 +1,7 @@
 import pytest
 
 from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
 from tests.test_cases import basic
 
 
@@ -45,3 +46,56 @@ def test_clear_simulations():
     assert experiment.get_simulations() == []
     assert not experiment.clear_simulations()
     assert experiment.get_simulations() == []
+
+def test_experiment_backend():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    experiment = Experiment([simulation, simulation])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = experiment.run()
+
+    for backend in ['serial', 'threads', 'processes']:
+        experiment = Experiment([simulation, simulation], backend=backend, workers=2)
+        experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, processes=1)
+        assert experiment.run() == result
+        # The Engine is only overridden for the run
+        assert experiment.engine.backend == Backend.SINGLE_PROCESS
+        assert experiment.engine.processes == 1
+
+    experiment = Experiment(simulation, backend='threads')
+    experiment.engine = Engine(capture_stdout=True)
+    with pytest.raises(ValueError, match="standard output"):
+        experiment.run()
+    assert experiment.engine.backend == Backend.DEFAULT
+
+    with pytest.raises(ValueError, match="Experiment backend must be one of"):
+        Experiment(simulation, backend='gpu')
+
+def test_experiment_reconcile_schema():
+    def update_c(params, substep, state_history, previous_state, policy_input):
+        return 'c', previous_state['c'] + 1
+
+    model = Model(initial_state=states, state_update_blocks=state_update_blocks, params=params)
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    other_model = Model(
+        initial_state={'a': 1.0, 'c': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'c': update_c}}],
+        params={},
+    )
+    other_simulation = Simulation(model=other_model, timesteps=2, runs=1)
+
+    experiment = Experiment([simulation, other_simulation], reconcile_schema='raise')
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    with pytest.raises(KeyError, match=r"State schema of simulation 1 doesn't match simulation 0: missing \['b'\], unexpected \['c'\]"):
+        experiment.run()
+
+    experiment = Experiment([simulation, other_simulation], reconcile_schema='fill', fill_value=-1)
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = experiment.run()
+    assert all(list(state) == list(result[0]) for state in result)
+    assert all(state['c'] == -1 for state in result if state['simulation'] == 0)
+    assert all(state['b'] == -1 for state in result if state['simulation'] == 1)
+
+    experiment = Experiment([simulation, simulation], reconcile_schema='raise')
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    experiment.run()
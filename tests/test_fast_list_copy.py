@@ -0,0 +1,47 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from radcad.core import _deepcopy_state
+
+
+def update_agents(params, substep, state_history, previous_state, policy_input):
+    agents = previous_state['agents']
+    for agent in agents:
+        agent['wealth'] += 1
+    return 'agents', agents
+
+initial_state = {
+    'agents': [{'id': i, 'wealth': 0} for i in range(10)],
+    'nested': [{'id': 0, 'history': [0]}],
+}
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {
+            'agents': update_agents,
+        }
+    },
+]
+
+def test_deepcopy_state():
+    state = {'agents': [{'id': 0, 'wealth': 0}], 'nested': [{'history': [0]}], 'a': 1}
+    copied = _deepcopy_state(state, fast_list_copy=True)
+
+    assert copied == state
+    assert list(copied.keys()) == list(state.keys())
+    assert copied['agents'][0] is not state['agents'][0]
+    assert copied['nested'][0]['history'] is not state['nested'][0]['history']
+
+def test_fast_list_copy():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=5)
+    experiment = Experiment(simulation)
+
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, fast_list_copy=True)
+    fast_result = experiment.run()
+
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = experiment.run()
+
+    assert fast_result == result
+    assert [state['agents'][0]['wealth'] for state in fast_result] == [0, 1, 2, 3, 4, 5]
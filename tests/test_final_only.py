@@ -0,0 +1,28 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_final_only():
+    states = basic.states
+    state_update_blocks = basic.state_update_blocks
+    params = basic.params
+    TIMESTEPS = 10
+    RUNS = 2
+
+    model = Model(initial_state=states, state_update_blocks=state_update_blocks, params=params)
+    simulation = Simulation(model=model, timesteps=TIMESTEPS, runs=RUNS)
+    experiment = Experiment(simulations=[simulation])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, final_only=True)
+
+    final_only_result = experiment.run()
+
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation_result = simulation.run()
+    final_states = [
+        state for state in simulation_result
+        if state['timestep'] == TIMESTEPS and state['substep'] == len(state_update_blocks)
+    ]
+
+    assert len(final_only_result) == RUNS * len(basic.params['a'])
+    assert final_only_result == final_states
@@ -0,0 +1,69 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.core import derive_run_seed
+from tests.test_cases import basic
+
+import pytest
+import random
+
+
+def test_from_matrix():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks)
+    rows = [
+        {'a': 1, 'b': 1, 'runs': 2},
+        {'a': 2, 'b': 1},
+    ]
+    simulation = Simulation.from_matrix(model, rows, timesteps=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert simulation.runs == 2
+    final_states = [(s['run'], s['subset']) for s in result if s['timestep'] == 2 and s['substep'] == 2]
+    assert final_states == [(1, 0), (1, 1), (2, 0)]
+
+def test_from_matrix_params():
+    params_seen = set()
+
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        params_seen.add((params['a'], params['b']))
+        return 'a', previous_state['a']
+
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}],
+        params={'a': [0], 'b': [0]},
+    )
+    rows = [{'a': 1, 'b': 2}, {'a': 3, 'b': 4}]
+    simulation = Simulation.from_matrix(model, rows, timesteps=1, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert params_seen == {(1, 2), (3, 4)}
+    assert len([s for s in result if s['timestep'] == 1]) == 4
+
+def test_from_matrix_missing_params():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    with pytest.raises(ValueError, match=r"row 1 is missing parameters \['b'\]"):
+        Simulation.from_matrix(model, [{'a': 1, 'b': 1}, {'a': 2}])
+
+def test_from_matrix_key_order():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation.from_matrix(model, [{'b': 1, 'c': 2, 'a': 1}])
+    assert list(simulation.param_matrix[0]) == ['runs', 'a', 'b', 'c']
+
+def test_from_matrix_seed():
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        return 'a', random.random()
+
+    model = Model(initial_state={'a': 0.0}, state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}], params={'b': [0]})
+    rows = [{'b': 1, 'seed': 1}, {'b': 1, 'seed': 2}, {'b': 1}]
+    simulation = Simulation.from_matrix(model, rows, timesteps=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, seed=1)
+    result = simulation.run()
+
+    assert all('seed' not in state for state in result)
+    assert [run['seed'] for run in simulation.exceptions] == [derive_run_seed(seed, 0, 0, subset) for (subset, seed) in enumerate([1, 2, 1])]
+
+    simulation.engine = Engine(backend=Backend.THREADS)
+    with pytest.raises(ValueError):
+        simulation.run()
@@ -0,0 +1,36 @@
+import pytest
+from types import MappingProxyType
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_reads(params, substep, state_history, previous_state):
+    assert isinstance(previous_state, MappingProxyType)
+    return {'delta': previous_state['a'] + 1}
+
+
+def policy_mutates(params, substep, state_history, previous_state):
+    previous_state['a'] = 10
+    return {'delta': 1}
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', policy_input['delta']
+
+
+def run_simulation(policy, **kwargs):
+    model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {'p': policy}, 'variables': {'a': update_a}}])
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, frozen_state=True, **kwargs)
+    return simulation.run()
+
+
+def test_frozen_state():
+    assert [state['a'] for state in run_simulation(policy_reads)] == [0, 1, 2, 3]
+    assert [state['a'] for state in run_simulation(policy_reads, check_policy_mutation=True)] == [0, 1, 2, 3]
+
+
+def test_frozen_state_mutation():
+    with pytest.raises(TypeError):
+        run_simulation(policy_mutates)
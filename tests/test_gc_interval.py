@@ -0,0 +1,26 @@
+import pytest
+from unittest import mock
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_gc_interval():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': 1, 'b': 1})
+    simulation = Simulation(model=model, timesteps=5, runs=1)
+
+    with mock.patch('radcad.core.gc.collect') as collect:
+        simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+        result = simulation.run()
+        collect.assert_not_called()
+
+        simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, gc_interval=3)
+        assert simulation.run() == result
+        # 5 timesteps of 2 substeps
+        assert collect.call_count == 3
+
+
+def test_gc_interval_invalid():
+    with pytest.raises(ValueError):
+        Engine(gc_interval=0)
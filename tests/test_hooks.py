@@ -1,4 +1,7 @@
-from radcad import Model, Simulation, Experiment
+import pytest
+
+from radcad import Model, Simulation, Experiment, set_pre_run_hook, set_post_run_hook
+from radcad.engine import Engine, Backend
 from tests.test_cases import basic
 
 
@@ -60,3 +63,113 @@ def test_hooks(capsys):
     after_simulation 1
     after_experiment
     """.replace('\n', '').replace(' ', '')
+
+def test_postprocess():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    result = simulation.run()
+
+    simulation.postprocess = lambda results: {'states': len(results)}
+    assert simulation.run() == {'states': len(result)}
+    assert simulation.results == result
+
+def test_on_run_finished():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    finished = []
+    simulation = Simulation(model=model, timesteps=1, runs=2)
+    simulation.on_run_finished = lambda simulation, run, subset, seconds: finished.append((simulation, run, subset, seconds))
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+
+    assert [(simulation, run, subset) for (simulation, run, subset, _) in finished] == [
+        (0, 0, 0), (0, 0, 1), (0, 1, 0), (0, 1, 1)
+    ]
+    assert all(seconds >= 0 for (_, _, _, seconds) in finished)
+
+def test_on_run_finished_raises():
+    def failing_hook(simulation, run, subset, seconds):
+        raise RuntimeError("Hook failed")
+
+    def failing_update(params, substep, state_history, previous_state, policy_input):
+        raise ValueError("Failed")
+
+    def update(params, substep, state_history, previous_state, policy_input):
+        return 'a', previous_state['a'] + 1
+
+    model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': update}}], params={})
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.on_run_finished = failing_hook
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert len(simulation.run()) == 2
+
+    # The error of the run isn't masked by the error of the hook
+    model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': failing_update}}], params={})
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.on_run_finished = failing_hook
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    with pytest.raises(ValueError, match="Failed"):
+        simulation.run()
+
+def test_module_run_hooks():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    experiment = Experiment(simulations=[simulation])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    calls = []
+    set_pre_run_hook(lambda executable=None: calls.append(('pre', executable)))
+    set_post_run_hook(lambda executable=None: calls.append(('post', executable, len(executable.results))))
+    try:
+        simulation.run()
+        experiment.run()
+        # Planning doesn't execute the runs
+        simulation.run(plan_only=True)
+    finally:
+        set_pre_run_hook(None)
+        set_post_run_hook(None)
+
+    assert calls == [
+        ('pre', simulation), ('post', simulation, len(simulation.results)),
+        ('pre', experiment), ('post', experiment, len(experiment.results)),
+    ]
+    simulation.run()
+    assert len(calls) == 4
+
+def test_module_post_run_hook_on_exception():
+    def failing_update(params, substep, state_history, previous_state, policy_input):
+        raise ValueError("Failed")
+
+    model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': failing_update}}], params={})
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    calls = []
+    set_post_run_hook(lambda executable=None: calls.append(executable))
+    try:
+        with pytest.raises(ValueError, match="Failed"):
+            simulation.run()
+    finally:
+        set_post_run_hook(None)
+
+    assert calls == [simulation]
+
+def test_module_post_run_hook_raises():
+    def failing_update(params, substep, state_history, previous_state, policy_input):
+        raise ValueError("Failed")
+
+    def failing_hook(executable=None):
+        raise RuntimeError("Hook failed")
+
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    failing_model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': failing_update}}], params={})
+    failing_simulation = Simulation(model=failing_model, timesteps=1, runs=1)
+    failing_simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    set_post_run_hook(failing_hook)
+    try:
+        assert simulation.run() == simulation.results
+        # The exception of the run isn't masked by the exception of the hook
+        with pytest.raises(ValueError, match="Failed"):
+            failing_simulation.run()
+    finally:
+        set_post_run_hook(None)
@@ -0,0 +1,54 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+
+
+def policy_moving_average(params, substep, state_history, previous_state):
+    window = [substeps[-1]['price'] for substeps in state_history[-3:]]
+    return {'average': sum(window) / len(window)}
+
+def update_price(params, substep, state_history, previous_state, policy_input):
+    return 'price', policy_input['average']
+
+initial_state = {
+    'price': 4.0
+}
+
+state_update_blocks = [
+    {
+        'policies': {
+            'p': policy_moving_average,
+        },
+        'variables': {
+            'price': update_price,
+        }
+    },
+]
+
+initial_history = [
+    [{'price': 1.0, 'timestep': 0, 'substep': 0}],
+    [{'price': 2.0, 'timestep': 1, 'substep': 1}],
+]
+
+def test_initial_history():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, initial_history=initial_history)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = simulation.run()
+
+    assert [state['timestep'] for state in result] == [0, 1, 2, 3, 4]
+    assert [state['price'] for state in result] == [1.0, 2.0, 4.0, 7 / 3, (2.0 + 4.0 + 7 / 3) / 3]
+    assert all(state['run'] == 1 and state['simulation'] == 0 for state in result)
+    # The Simulation's initial history isn't mutated
+    assert 'run' not in simulation.initial_history[0][0]
+
+def test_initial_history_invalid_schema():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, initial_history=[[{'cost': 1.0}]])
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(ValueError) as e:
+        simulation.run()
+    assert str(e.value) == "Initial history record at timestep 0 doesn't match initial state keys: missing ['price'], unexpected ['cost']"
@@ -0,0 +1,33 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import pytest
+
+
+def test_initial_substep():
+    params = {'a': [1], 'b': [1]}
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=params)
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    # Resume from a checkpoint captured after the first substep of timestep 2
+    checkpoint = {key: value for key, value in result[3].items() if key in basic.states}
+    assert (result[3]['timestep'], result[3]['substep']) == (2, 1)
+
+    resumed_model = Model(initial_state={**checkpoint, 'timestep': 2}, state_update_blocks=basic.state_update_blocks, params=params)
+    resumed_simulation = Simulation(model=resumed_model, timesteps=1, runs=1, initial_substep=1)
+    resumed_simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    resumed_result = resumed_simulation.run()
+
+    assert [(state['timestep'], state['substep']) for state in resumed_result] == [(2, 1), (2, 2), (3, 1), (3, 2)]
+    assert resumed_result == result[3:]
+
+def test_invalid_initial_substep():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=3, runs=1, initial_substep=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(ValueError):
+        simulation.run()
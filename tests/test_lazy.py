@@ -0,0 +1,60 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def run(sequential_variables=False, record_updated=False):
+    calls = []
+
+    def policy_rate(params, substep, state_history, previous_state):
+        # The rate only changes every other timestep
+        return {'rate': (previous_state['timestep'] + 1) // 2, 'noise': previous_state['timestep']}
+
+    def update_price(params, substep, state_history, previous_state, policy_input):
+        calls.append(previous_state['timestep'])
+        return 'price', 100 * policy_input['rate']
+
+    def update_count(params, substep, state_history, previous_state, policy_input):
+        return 'count', previous_state['count'] + 1
+
+    state_update_blocks = [
+        {
+            'policies': {'rate': policy_rate},
+            'variables': {'price': update_price, 'count': update_count},
+            'lazy': {'price': ['rate']},
+            'sequential_variables': sequential_variables,
+        },
+    ]
+    model = Model(initial_state={'price': 0, 'count': 0}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=6, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_updated=record_updated)
+    return simulation.run(), calls
+
+
+def test_lazy():
+    for sequential_variables in [False, True]:
+        result, calls = run(sequential_variables)
+        # Only called when the rate signal changes, keeping the prior value otherwise
+        assert calls == [0, 1, 3, 5]
+        assert [state['price'] for state in result] == [0, 0, 100, 100, 200, 200, 300]
+        assert [state['count'] for state in result] == list(range(7))
+
+
+def test_lazy_record_updated():
+    for sequential_variables in [False, True]:
+        result, _ = run(sequential_variables, record_updated=True)
+        # Skipped State Update Functions aren't recorded as updated
+        assert [state['__updated__'] for state in result] == [
+            [], ['price', 'count'], ['price', 'count'], ['count'], ['price', 'count'], ['count'], ['price', 'count']
+        ]
+
+
+def test_lazy_invalid_key():
+    model = Model(
+        initial_state={'price': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {}, 'lazy': {'price': ['rate']}}],
+        params={},
+    )
+    with pytest.raises(KeyError):
+        Simulation(model=model, timesteps=1).compile()
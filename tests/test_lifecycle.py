@@ -0,0 +1,110 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+
+
+class PriceOracle:
+    def __init__(self):
+        self.events = []
+        self.connection = None
+
+    def __enter__(self):
+        self.events.append('enter')
+        self.connection = 0
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.events.append('exit')
+        self.connection = None
+
+    def policy_price(self, params, substep, state_history, previous_state):
+        self.connection += 1
+        return {'price': self.connection}
+
+    def update_price(self, params, substep, state_history, previous_state, policy_input):
+        return 'price', policy_input['price']
+
+
+class Counter:
+    def __init__(self, fail=False):
+        self.events = []
+        self.fail = fail
+
+    def setup(self):
+        self.events.append('setup')
+
+    def teardown(self):
+        self.events.append('teardown')
+
+    def update_count(self, params, substep, state_history, previous_state, policy_input):
+        if self.fail:
+            raise Exception('Forced exception from state update function')
+        return 'count', previous_state['count'] + 1
+
+initial_state = {
+    'price': 0,
+    'count': 0,
+}
+
+def test_lifecycle():
+    oracle = PriceOracle()
+    counter = Counter()
+    state_update_blocks = [
+        {
+            'policies': {
+                'price': oracle.policy_price,
+            },
+            'variables': {
+                'price': oracle.update_price,
+                'count': counter.update_count,
+            }
+        },
+    ]
+
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=3, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, manage_lifecycle=True)
+    result = simulation.run()
+
+    # self is preserved across calls within a run, and resources are acquired and released per run
+    assert [state['price'] for state in result] == [0, 1, 2, 3, 0, 1, 2, 3]
+    assert oracle.events == ['enter', 'exit', 'enter', 'exit']
+    assert counter.events == ['setup', 'teardown', 'setup', 'teardown']
+
+def test_lifecycle_exception():
+    counter = Counter(fail=True)
+    state_update_blocks = [
+        {
+            'policies': {},
+            'variables': {
+                'count': counter.update_count,
+            }
+        },
+    ]
+
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, manage_lifecycle=True)
+
+    with pytest.raises(Exception):
+        simulation.run()
+    assert counter.events == ['setup', 'teardown']
+
+def test_lifecycle_disabled():
+    counter = Counter()
+    state_update_blocks = [
+        {
+            'policies': {},
+            'variables': {
+                'count': counter.update_count,
+            }
+        },
+    ]
+
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+    # Objects aren't acquired and released unless enabled
+    assert counter.events == []
@@ -0,0 +1,66 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.utils import melt_states
+
+
+def update_price(params, substep, state_history, previous_state, policy_input):
+    return 'price', previous_state['price'] * 2
+
+def update_supply(params, substep, state_history, previous_state, policy_input):
+    return 'supply', previous_state['supply'] - 1
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'price': update_price, 'supply': update_supply},
+    },
+]
+
+
+def simulation(**kwargs):
+    model = Model(initial_state={'price': 1.0, 'supply': 10, 'name': 'token'}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation
+
+
+def test_melt():
+    sim = simulation(melt=['price', 'supply'])
+    result = sim.run()
+    assert [(row['timestep'], row['variable'], row['value']) for row in result] == [
+        (0, 'price', 1.0), (0, 'supply', 10),
+        (1, 'price', 2.0), (1, 'supply', 9),
+        (2, 'price', 4.0), (2, 'supply', 8),
+    ]
+    # Columns that aren't melted are retained as index columns
+    assert result[0] == {'name': 'token', 'simulation': 0, 'subset': 0, 'run': 1, 'substep': 0, 'timestep': 0, 'variable': 'price', 'value': 1.0}
+    assert list(sim.result_schema()) == list(result[0])
+    assert sim.result_schema()['value'] is float
+
+
+def test_melt_spilled():
+    result = simulation(melt=['supply'], spill_threshold=2).run()
+    assert [row['value'] for row in result] == [10, 9, 8]
+    assert all('supply' not in row and row['price'] for row in result)
+
+
+def test_melt_states():
+    states = [{'a': 1, 'b': 2.0, 'timestep': 0}]
+    assert list(melt_states(states, ['b'])) == [{'a': 1, 'timestep': 0, 'variable': 'b', 'value': 2.0}]
+
+
+def test_melt_non_numeric():
+    with pytest.raises(TypeError, match="Melted State Variable name must be numeric, not str"):
+        simulation(melt=['name']).run()
+
+
+def test_melt_invalid_key():
+    with pytest.raises(KeyError, match="Invalid state keys"):
+        simulation(melt=['price', 'demand']).run()
+
+
+def test_melt_invalid_option():
+    with pytest.raises(ValueError, match="Melted State Variables must be a non-empty list of keys"):
+        simulation(melt='price')
@@ -0,0 +1,54 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.results import SpilledResults
+from radcad.utils import extract_exceptions
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {'a': update_a},
+    },
+]
+
+
+def simulation(**kwargs):
+    model = Model(initial_state={'a': 0}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=20, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation
+
+
+def test_within_memory_budget():
+    result = simulation().run(max_memory_mb=1_000_000)
+    assert [state['a'] for state in result] == list(range(21)) * 2
+
+
+def test_exceeding_memory_budget():
+    with pytest.raises(MemoryError, match="exceeds the memory budget of 0.001 MB at timestep 0 of simulation 0 / run 0 / subset 0"):
+        simulation().run(max_memory_mb=0.001)
+
+
+def test_exceeding_memory_budget_collecting_results():
+    # Run exceptions are returned, while exceeding the memory budget collecting results is always raised
+    with pytest.raises(MemoryError, match="collecting results, consider using the spill_threshold Engine option"):
+        simulation(raise_exceptions=False).run(max_memory_mb=0.001)
+
+
+def test_spilling_over_memory_budget():
+    runs = [([[{'a': index}]], None) for index in range(3)]
+    results, _ = extract_exceptions(runs, spill_threshold=10, max_memory_mb=0.001)
+    assert isinstance(results, SpilledResults)
+    # States are spilled as each run is collected, before the spill threshold is reached
+    assert results._spilled_length == 3
+    assert list(results) == [{'a': 0}, {'a': 1}, {'a': 2}]
+
+
+def test_invalid_memory_budget():
+    with pytest.raises(ValueError, match="Maximum memory must be a positive number of MB"):
+        simulation().run(max_memory_mb=0)
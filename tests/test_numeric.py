@@ -0,0 +1,80 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.numeric import run_numeric
+
+import pytest
+
+
+def policy(params, substep, previous_state):
+    return {'step': params['step']}
+
+def update_a(params, substep, previous_state, policy_input):
+    return previous_state['a'] + policy_input['step']
+
+def update_b(params, substep, previous_state, policy_input):
+    return previous_state['a'] * 0.5
+
+def test_run_numeric():
+    state_update_blocks = [
+        {'policies': {'p_1': policy, 'p_2': policy}, 'variables': {'a': update_a}},
+        {'policies': {}, 'variables': {'b': update_b}},
+    ]
+    columns = run_numeric({'a': 0, 'b': 0.0}, state_update_blocks, params={'step': [1]}, timesteps=3)
+
+    assert columns['a'].typecode == 'q' and columns['b'].typecode == 'd'
+    assert list(columns['timestep']) == [0, 1, 1, 2, 2, 3, 3]
+    assert list(columns['substep']) == [0, 1, 2, 1, 2, 1, 2]
+    assert list(columns['a']) == [0, 2, 2, 4, 4, 6, 6]
+    assert list(columns['b']) == [0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0]
+
+def test_run_numeric_matches_engine():
+    state_update_blocks = [
+        {'policies': {'p_1': policy}, 'variables': {'a': update_a}},
+    ]
+    columns = run_numeric({'a': 1.0}, state_update_blocks, params={'step': 2}, timesteps=5)
+
+    model = Model(
+        initial_state={'a': 1.0},
+        state_update_blocks=[{
+            'policies': {'p_1': lambda params, substep, state_history, previous_state: policy(params, substep, previous_state)},
+            'variables': {'a': lambda params, substep, state_history, previous_state, policy_input: ('a', update_a(params, substep, previous_state, policy_input))},
+        }],
+        params={'step': 2},
+    )
+    simulation = Simulation(model=model, timesteps=5, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert list(columns['a']) == [state['a'] for state in simulation.run()]
+
+def test_run_numeric_substep_matches_engine():
+    def policy_substep(params, substep, previous_state):
+        return {'substep': substep}
+
+    def update_substeps(params, substep, previous_state, policy_input):
+        return previous_state['substeps'] + 10 * substep + policy_input['substep']
+
+    state_update_blocks = [
+        {'policies': {'p_1': policy_substep}, 'variables': {'substeps': update_substeps}},
+        {'policies': {'p_1': policy_substep}, 'variables': {'substeps': update_substeps}},
+    ]
+    columns = run_numeric({'substeps': 0}, state_update_blocks, timesteps=2)
+
+    model = Model(
+        initial_state={'substeps': 0},
+        state_update_blocks=[{
+            'policies': {'p_1': lambda params, substep, state_history, previous_state: policy_substep(params, substep, previous_state)},
+            'variables': {'substeps': lambda params, substep, state_history, previous_state, policy_input: ('substeps', update_substeps(params, substep, previous_state, policy_input))},
+        }] * 2,
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    # Functions are passed the zero-based substep, and the recorded substep is one-based, as in the Engine
+    assert list(columns['substeps']) == [state['substeps'] for state in result] == [0, 0, 11, 11, 22]
+    assert list(columns['substep']) == [state['substep'] for state in result]
+
+def test_run_numeric_invalid_state():
+    with pytest.raises(TypeError, match="State Variable a must be numeric"):
+        run_numeric({'a': 'x'}, [])
+    with pytest.raises(ValueError, match="parameter sweeps"):
+        run_numeric({'a': 1}, [], params={'step': [1, 2]})
@@ -0,0 +1,30 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_one_based_run():
+    contexts = []
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=2, runs=2)
+    simulation.before_run = lambda context: contexts.append(context.run)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert sorted({state['run'] for state in result}) == [1, 2]
+    # By default, the run index passed to hooks is zero-based
+    assert contexts == [0, 1]
+
+    contexts.clear()
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, one_based_run=True)
+    one_based_result = simulation.run()
+
+    assert sorted({state['run'] for state in one_based_result}) == contexts == [1, 2]
+    assert one_based_result == result
+
+    contexts.clear()
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, one_based_run=False)
+    zero_based_result = simulation.run()
+
+    assert sorted({state['run'] for state in zero_based_result}) == contexts == [0, 1]
+    assert zero_based_result == [{**state, 'run': state['run'] - 1} for state in result]
@@ -0,0 +1,45 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_fee(params, substep, state_history, previous_state):
+    return {'fee': params['fee']}
+
+
+def update_fees(params, substep, state_history, previous_state, policy_input):
+    return 'fees', previous_state['fees'] + [(policy_input['fee'], params['fee'])]
+
+
+def test_param_overrides():
+    model = Model(
+        initial_state={'fees': []},
+        state_update_blocks=[
+            {'policies': {'p': policy_fee}, 'variables': {'fees': update_fees}},
+            {'policies': {'p': policy_fee}, 'variables': {'fees': update_fees}, 'param_overrides': {'fee': 0.3}},
+        ],
+        params={'fee': [0.1, 0.2]},
+    )
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert [state['fees'] for state in result if state['substep'] == 2] == [
+        [(0.1, 0.1), (0.3, 0.3)],
+        [(0.2, 0.2), (0.3, 0.3)],
+    ]
+
+
+def test_param_overrides_unknown_key():
+    model = Model(
+        initial_state={'fees': []},
+        state_update_blocks=[{'policies': {}, 'variables': {'fees': update_fees}, 'param_overrides': {'tier': 1}}],
+        params={'fee': 0.1},
+    )
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(KeyError) as e:
+        simulation.run()
+    assert "['tier']" in str(e.value)
@@ -0,0 +1,105 @@
+from radcad import Model, Simulation, Scenarios
+from radcad.core import generate_parameter_sweep
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+from unittest import mock
+import pytest
+
+
+def test_parameter_sweep_cache():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    param_sweep = simulation.parameter_sweep()
+    assert param_sweep == [{'a': 1, 'b': 1}, {'a': 2, 'b': 1}]
+
+    with mock.patch('radcad.wrappers.generate_parameter_sweep') as generate_parameter_sweep:
+        assert simulation.parameter_sweep() is param_sweep
+        result = simulation.run()
+        simulation.run()
+        generate_parameter_sweep.assert_not_called()
+    assert len(result) == 2 * (10 * len(basic.state_update_blocks) + 1)
+
+    # Reassigning params invalidates the cache
+    simulation.model.params = {'a': [3], 'b': [1]}
+    assert simulation.parameter_sweep() == [{'a': 3, 'b': 1}]
+    assert simulation.run() != result
+
+    # Mutating params in place invalidates the cache
+    simulation.model.params['a'].append(4)
+    assert simulation.parameter_sweep() == [{'a': 3, 'b': 1}, {'a': 4, 'b': 1}]
+    simulation.model.params['a'].pop()
+
+    # Reassigning the Model invalidates the cache
+    simulation.model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    assert simulation.parameter_sweep() == param_sweep
+    assert simulation.run() == result
+
+def test_no_sweep():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1, 2], 'c': 3})
+    simulation = Simulation(model=model, timesteps=10, runs=1, sweep=False)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert simulation.parameter_sweep() == [{'a': 1, 'b': [1, 2], 'c': 3}]
+    assert {state['subset'] for state in simulation.run()} == {0}
+
+    simulation.sweep = True
+    assert len(simulation.parameter_sweep()) == 2
+
+
+def test_param_sets():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    param_sets = [{'a': 1, 'b': [1, 2]}, {'a': 3, 'b': [3]}]
+    simulation = Simulation(model=model, timesteps=1, runs=1, param_sets=param_sets)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with mock.patch('radcad.wrappers.generate_parameter_sweep') as generate_parameter_sweep:
+        assert simulation.parameter_sweep() is param_sets
+        result = simulation.run()
+        generate_parameter_sweep.assert_not_called()
+    assert sorted({state['subset'] for state in result}) == [0, 1]
+
+
+def test_param_sets_invalid():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    for param_sets in [[], [1, 2]]:
+        with pytest.raises(ValueError):
+            Simulation(model=model, param_sets=param_sets)
+
+
+def test_scenarios():
+    params = {
+        'regime': Scenarios({'bull': {'drift': 0.1}, 'bear': {'drift': -0.1}}),
+        'volatility': [0.1, 0.2],
+        'fee': 0.01,
+    }
+    # Scenarios are swept like a list of their parameter values, alongside other swept parameters
+    assert generate_parameter_sweep(params) == [
+        {'regime': {'drift': 0.1}, 'regime_scenario': 'bull', 'volatility': 0.1, 'fee': 0.01},
+        {'regime': {'drift': -0.1}, 'regime_scenario': 'bear', 'volatility': 0.2, 'fee': 0.01},
+    ]
+    # Dicts that aren't Scenarios are single parameter values
+    assert generate_parameter_sweep({'regime': {'drift': 0.1}}) == [{'regime': {'drift': 0.1}}]
+
+    with pytest.raises(ValueError):
+        generate_parameter_sweep({'regime': Scenarios()})
+
+
+def test_scenarios_records():
+    def update_b(params, substep, state_history, previous_state, policy_input):
+        return 'b', previous_state['b'] + params['regime']['drift']
+
+    model = Model(
+        initial_state={'b': 0.0},
+        state_update_blocks=[{'policies': {}, 'variables': {'b': update_b}}],
+        params={'regime': Scenarios({'bull': {'drift': 1.0}, 'bear': {'drift': -1.0}})},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert {(state['regime_scenario'], state['b']) for state in result if state['timestep'] == 2} == {('bull', 2.0), ('bear', -2.0)}
+    assert list(simulation.result_schema()) == list(result[0])
+    assert simulation.result_schema()['regime_scenario'] is str
@@ -0,0 +1,25 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def test_params_immutable():
+    params_seen = []
+
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        params_seen.append(params)
+        return 'a', previous_state['a'] + params['step']
+
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}],
+        params={'step': [1], 'table': [list(range(1000))]},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=3)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+    assert len({id(params) for params in params_seen}) == 3
+
+    params_seen.clear()
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, params_immutable=True)
+    assert simulation.run() == result
+    assert len({id(params) for params in params_seen}) == 1
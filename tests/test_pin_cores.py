@@ -0,0 +1,32 @@
+import os
+import pytest
+from unittest import mock
+
+from radcad import Model, Simulation
+from radcad.core import _pin_to_core
+from radcad.engine import Engine, Backend
+
+
+def update_cores(params, substep, state_history, previous_state, policy_input):
+    return 'cores', sorted(os.sched_getaffinity(0))
+
+
+model = Model(initial_state={'cores': []}, state_update_blocks=[{'policies': {}, 'variables': {'cores': update_cores}}])
+
+
+@pytest.mark.skipif(not hasattr(os, 'sched_setaffinity'), reason="CPU affinity is only supported on Linux")
+def test_pin_cores():
+    simulation = Simulation(model=model, timesteps=1, runs=4)
+    simulation.engine = Engine(backend=Backend.MULTIPROCESSING, processes=2, pin_cores=True)
+    result = simulation.run()
+
+    assert all(len(state['cores']) == 1 for state in result if state['timestep'] == 1)
+
+
+@pytest.mark.skipif(not hasattr(os, 'sched_setaffinity'), reason="CPU affinity is only supported on Linux")
+def test_pin_cores_calling_process():
+    affinity = os.sched_getaffinity(0)
+    with mock.patch('os.sched_setaffinity') as sched_setaffinity:
+        _pin_to_core()
+        sched_setaffinity.assert_not_called()
+    assert os.sched_getaffinity(0) == affinity
@@ -0,0 +1,66 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def model():
+    return Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+
+
+def test_plan_only():
+    simulation = Simulation(model=model(), timesteps=10, runs=3)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    hook_calls = []
+    simulation.before_run = lambda context: hook_calls.append(context)
+
+    plan = simulation.run(plan_only=True)
+    assert plan == {
+        'simulations': 1,
+        'units': 6,
+        'timesteps': 60,
+        'records': 6 * (1 + 10 * 2),
+        'plan': [{'simulation': 0, 'runs': 6, 'subsets': 2, 'units': 6, 'timesteps': 10, 'records': 126}],
+    }
+    # Nothing is executed
+    assert hook_calls == []
+    assert simulation.results == []
+
+    result = simulation.run()
+    assert len(result) == plan['records']
+
+
+def test_plan_only_experiment():
+    experiment = Experiment([
+        Simulation(model=model(), timesteps=10, runs=1),
+        Simulation(model=model(), timesteps=5, runs=2, sweep=False),
+    ])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, drop_substeps=True)
+
+    plan = experiment.run(plan_only=True, only=[(0, 0, 0), (1, 0, 0), (1, 1, 0)])
+    assert plan['simulations'] == 2
+    assert [(simulation['subsets'], simulation['units']) for simulation in plan['plan']] == [(2, 1), (1, 2)]
+    assert plan['timesteps'] == 10 + 2 * 5
+    assert plan['records'] == (1 + 10) + 2 * (1 + 5)
+
+
+def test_plan_only_record_stride():
+    simulation = Simulation(model=model(), timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_stride=4)
+    plan = simulation.run(plan_only=True)
+    assert plan['records'] == len(simulation.run())
+
+
+def test_plan_only_from_matrix():
+    rows = [{'a': 1, 'b': 1, 'runs': 3}, {'a': 2, 'b': 1}]
+    simulation = Simulation.from_matrix(model(), rows, timesteps=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    plan = simulation.run(plan_only=True)
+    assert [(simulation['runs'], simulation['units']) for simulation in plan['plan']] == [(4, 4)]
+    assert plan['records'] == len(simulation.run())
+
+
+def test_plan_only_upper_bound():
+    simulation = Simulation(model=model(), timesteps=10, runs=1, record_when=lambda state: state['timestep'] < 5)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    plan = simulation.run(plan_only=True)
+    assert plan['records'] > len(simulation.run())
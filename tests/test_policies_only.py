@@ -0,0 +1,27 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_policies_only():
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        raise Exception('State Update Functions should not be executed')
+
+    model = Model(
+        initial_state=basic.states,
+        state_update_blocks=[
+            {'policies': {}, 'variables': {'a': update_a}},
+            {**basic.state_update_blocks[1], 'variables': {'b': update_a}},
+        ],
+        params=basic.params,
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, policies_only=True)
+    result = simulation.run()
+
+    assert result == [
+        {'simulation': 0, 'subset': subset, 'run': 1, 'timestep': timestep, 'substep': substep, 'signals': signals}
+        for subset in [0, 1]
+        for timestep in [1, 2]
+        for (substep, signals) in [(1, {}), (2, {'step_size': 5})]
+    ]
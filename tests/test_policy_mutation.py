@@ -0,0 +1,49 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+
+
+def policy_pure(params, substep, state_history, previous_state):
+    return {'delta': len(previous_state['items'])}
+
+def policy_mutates(params, substep, state_history, previous_state):
+    previous_state['items'].append(1)
+    return {'delta': 1}
+
+def update_count(params, substep, state_history, previous_state, policy_input):
+    return 'count', previous_state['count'] + policy_input['delta']
+
+initial_state = {
+    'items': [0],
+    'count': 0,
+}
+
+def state_update_blocks(policy):
+    return [
+        {
+            'policies': {
+                'pure': policy_pure,
+                'policy': policy,
+            },
+            'variables': {
+                'count': update_count,
+            }
+        },
+    ]
+
+def test_check_policy_mutation():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks(policy_mutates), params={})
+    simulation = Simulation(model=model, timesteps=5)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, check_policy_mutation=True)
+
+    with pytest.raises(RuntimeError) as e:
+        simulation.run()
+    assert str(e.value) == "Policy function policy mutated the state passed to it at substep 1"
+
+def test_check_policy_mutation_pure():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks(policy_pure), params={})
+    simulation = Simulation(model=model, timesteps=5)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, check_policy_mutation=True)
+
+    assert simulation.run()[-1]['count'] == 10
@@ -0,0 +1,33 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from radcad.core import merge_profiles
+from tests.test_cases import basic
+
+
+def test_profile():
+    TIMESTEPS = 10
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=TIMESTEPS, runs=1)
+    experiment = Experiment(simulation)
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, profile=True)
+
+    result = experiment.run()
+    subsets = len(basic.params['a'])
+
+    assert set(experiment.profile.keys()) == {'variable:a', 'variable:b', 'policy:p_1', 'policy:p_2', 'policy:p_3', 'policy:p_4', 'policy:p_5'}
+    assert experiment.profile['variable:a']['calls'] == TIMESTEPS * subsets
+    assert experiment.profile['policy:p_1']['seconds'] > 0
+    assert all(run['profile']['variable:b']['calls'] == TIMESTEPS for run in experiment.exceptions)
+
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert experiment.run() == result
+
+def test_merge_profiles():
+    profiles = [
+        {'policy:p': {'calls': 1, 'seconds': 1.0}},
+        {'policy:p': {'calls': 2, 'seconds': 0.5}, 'variable:a': {'calls': 1, 'seconds': 0.25}},
+    ]
+    assert merge_profiles(profiles) == {
+        'policy:p': {'calls': 3, 'seconds': 1.5},
+        'variable:a': {'calls': 1, 'seconds': 0.25},
+    }
@@ -0,0 +1,46 @@
+from unittest import mock
+
+from radcad import Model, Simulation
+from radcad.core import reduce_signals, _normalize_psu
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_psu_aliases():
+    state_update_blocks = [
+        {
+            'behaviors': psu['policies'],
+            'states': psu['variables'],
+        }
+        for psu in basic.state_update_blocks
+    ]
+
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    simulation.model = Model(initial_state=basic.states, state_update_blocks=state_update_blocks, params=basic.params)
+    assert simulation.run() == result
+    assert simulation.compile()
+
+def test_reduce_signals_aliases():
+    psu = {
+        'behaviors': {
+            '1': lambda params, substep, state_history, previous_state: {'signal_a': 1.0},
+            '2': lambda params, substep, state_history, previous_state: {'signal_a': 2.0},
+        },
+        'states': {},
+    }
+    assert reduce_signals({}, 1, [], {}, psu) == {'signal_a': 3.0}
+
+
+def test_psu_normalized_once_per_run():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with mock.patch('radcad.core._normalize_psu', wraps=_normalize_psu) as normalize_psu:
+        simulation.run()
+    # Each PSU is normalized once per run, rather than every substep
+    assert normalize_psu.call_count == len(basic.state_update_blocks)
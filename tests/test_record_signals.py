@@ -0,0 +1,72 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_delta(params, substep, state_history, previous_state):
+    assert 'signal_delta' not in previous_state
+    return {'delta': previous_state['a'] + 1}
+
+
+def policy_fee(params, substep, state_history, previous_state):
+    return {'fee': 0.1}
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', policy_input.get('delta', previous_state['a'])
+
+
+def test_record_signals():
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[
+            {'policies': {'p': policy_delta}, 'variables': {'a': update_a}},
+            {'policies': {'p': policy_fee}, 'variables': {'a': update_a}},
+        ],
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_signals=True)
+    result = simulation.run()
+
+    assert [
+        {key: value for (key, value) in state.items() if key.startswith('signal_') or key == 'a'}
+        for state in result
+    ] == [
+        {'a': 0},
+        {'a': 1, 'signal_delta': 1},
+        {'a': 1, 'signal_fee': 0.1},
+        {'a': 2, 'signal_delta': 2},
+        {'a': 2, 'signal_fee': 0.1},
+    ]
+
+    schema = simulation.result_schema()
+    assert set(schema) == {key for state in result for key in state}
+    assert schema['signal_delta'] is int and schema['signal_fee'] is float
+
+
+def test_record_signals_state_variables():
+    def update_signal_count(params, substep, state_history, previous_state, policy_input):
+        return 'signal_count', previous_state['signal_count'] + 1
+
+    # State Variables prefixed with signal_ aren't removed from the following substeps
+    model = Model(
+        initial_state={'a': 0, 'signal_count': 0},
+        state_update_blocks=[
+            {'policies': {'p': policy_delta}, 'variables': {'a': update_a, 'signal_count': update_signal_count}},
+            {'policies': {'p': policy_fee}, 'variables': {'signal_count': update_signal_count}},
+        ],
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_signals=True)
+    assert [state['signal_count'] for state in simulation.run()] == [0, 1, 2, 3, 4]
+
+    # A recorded signal can't replace a State Variable
+    model = Model(
+        initial_state={'a': 0, 'signal_fee': 0},
+        state_update_blocks=[{'policies': {'p': policy_fee}, 'variables': {'a': update_a}}],
+    )
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_signals=True)
+    with pytest.raises(ValueError, match="conflict with State Variables"):
+        simulation.run()
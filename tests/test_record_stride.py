@@ -0,0 +1,23 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def run(timesteps, **engine_options):
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=timesteps, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **engine_options)
+    return simulation.run()
+
+def test_record_stride():
+    result = run(10)
+    strided_result = run(10, record_stride=4)
+
+    assert sorted({state['timestep'] for state in strided_result}) == [0, 4, 8, 10]
+    assert strided_result == [state for state in result if state['timestep'] in [0, 4, 8, 10]]
+
+def test_record_stride_drop_substeps():
+    strided_result = run(9, record_stride=3, drop_substeps=True)
+    assert [(state['subset'], state['timestep']) for state in strided_result] == [
+        (subset, timestep) for subset in [0, 1] for timestep in [0, 3, 6, 9]
+    ]
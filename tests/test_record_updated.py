@@ -0,0 +1,15 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_record_updated():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, record_updated=True, strict_schema=True)
+    result = simulation.run()
+
+    assert [state['__updated__'] for state in result if state['subset'] == 0] == [[], ['a'], ['b'], ['a'], ['b']]
+
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert [{k: v for k, v in state.items() if k != '__updated__'} for state in result] == simulation.run()
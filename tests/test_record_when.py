@@ -0,0 +1,39 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_liquidation(params, substep, state_history, previous_state):
+    return {'liquidated': previous_state['timestep'] % 4 == 3 and substep == 1}
+
+def update_liquidated(params, substep, state_history, previous_state, policy_input):
+    return 'liquidated', policy_input['liquidated']
+
+state_update_blocks = [
+    {
+        'policies': {'liquidation': policy_liquidation},
+        'variables': {'liquidated': update_liquidated},
+    },
+    {
+        'policies': {'liquidation': policy_liquidation},
+        'variables': {'liquidated': update_liquidated},
+    },
+]
+
+
+def run(**kwargs):
+    model = Model(initial_state={'liquidated': False}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=10, runs=1, record_when=lambda state: state['liquidated'])
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation.run()
+
+
+def test_record_when():
+    result = run()
+    # The initial and final states are always recorded
+    assert [(state['timestep'], state['substep']) for state in result] == [(0, 0), (3, 2), (7, 2), (10, 2)]
+    assert [state['liquidated'] for state in result] == [False, True, True, False]
+
+
+def test_record_when_record_stride():
+    result = run(record_stride=7)
+    assert [(state['timestep'], state['substep']) for state in result] == [(0, 0), (7, 2), (10, 2)]
@@ -0,0 +1,26 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import pytest
+
+
+def test_run_metadata():
+    def run_metadata(simulation, run, subset, param_set):
+        return {'scenario': f"a={param_set['a']}", 'git_hash': 'abc123', 'run_index': run}
+
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=2, runs=2, run_metadata=run_metadata)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    assert all(state['git_hash'] == 'abc123' for state in result)
+    assert all(state['scenario'] == f"a={basic.params['a'][state['subset']]}" for state in result)
+    assert all(state['run_index'] == state['run'] - 1 for state in result)
+
+def test_run_metadata_conflict():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=2, runs=1, run_metadata=lambda **kwargs: {'a': 1})
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    with pytest.raises(ValueError, match=r"Run metadata keys \['a'\] conflict with State Variables"):
+        simulation.run()
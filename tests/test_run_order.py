@@ -0,0 +1,37 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import pytest
+
+
+def test_run_order():
+    executed = []
+
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        if previous_state['timestep'] == 0:
+            executed.append((previous_state['run'], previous_state['subset']))
+        return 'a', previous_state['a'] + params['a']
+
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}],
+        params={'a': [1, 2]},
+    )
+    simulation = Simulation(model=model, timesteps=3, runs=3)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+    sequential = list(executed)
+
+    for (run_order, seed) in [('reverse', None), ('shuffle', 1), ('shuffle', 2)]:
+        executed.clear()
+        simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, run_order=run_order, run_order_seed=seed)
+        assert simulation.run() == result
+        assert sorted(executed) == sorted(sequential)
+        assert executed != sequential
+        if run_order == 'reverse':
+            assert executed == sequential[::-1]
+
+def test_invalid_run_order():
+    with pytest.raises(ValueError, match="Run order must be one of"):
+        Engine(run_order='random')
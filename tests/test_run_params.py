@@ -0,0 +1,45 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from radcad.core import select_run_params
+
+import pytest
+
+
+def update_label(params, substep, state_history, previous_state, policy_input):
+    return 'label', f"{params['scenario']}-{params['a']}"
+
+initial_state = {
+    'label': None
+}
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {
+            'label': update_label,
+        }
+    },
+]
+
+def test_select_run_params():
+    run_params = {'scenario': ['low', 'high'], 'seed': [1]}
+    assert select_run_params(run_params, 0) == {'scenario': 'low', 'seed': 1}
+    assert select_run_params(run_params, 2) == {'scenario': 'high', 'seed': 1}
+
+def test_run_params():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={'a': [1, 2]})
+    simulation = Simulation(model=model, timesteps=1, runs=2, run_params={'scenario': ['low', 'high']})
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    result = simulation.run()
+    labels = [(state['run'], state['subset'], state['label']) for state in result if state['timestep'] == 1]
+
+    assert labels == [(1, 0, 'low-1'), (1, 1, 'low-2'), (2, 0, 'high-1'), (2, 1, 'high-2')]
+
+def test_run_params_conflict():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={'scenario': ['low']})
+    simulation = Simulation(model=model, timesteps=1, runs=2, run_params={'scenario': ['low', 'high']})
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    with pytest.raises(ValueError):
+        simulation.run()
@@ -0,0 +1,38 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from radcad.core import generate_run_uuid
+from tests.test_cases import basic
+
+
+def test_run_uuid():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=5, runs=2)
+    experiment = Experiment(simulation)
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, run_uuid=True)
+
+    result = experiment.run()
+    rerun_result = experiment.run()
+
+    assert all(state['run_uuid'] == generate_run_uuid(state['simulation'], state['run'], state['subset']) for state in result)
+    assert len({state['run_uuid'] for state in result}) == 2 * len(basic.params['a'])
+    assert [state['run_uuid'] for state in result] == [state['run_uuid'] for state in rerun_result]
+
+def test_run_uuid_disabled():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=5, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert all('run_uuid' not in state for state in simulation.run())
+
+
+def test_run_uuid_seed():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, run_uuid=True, seed=1)
+    seeded = {state['run_uuid'] for state in simulation.run()}
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, run_uuid=True, seed=2)
+    reseeded = {state['run_uuid'] for state in simulation.run()}
+
+    assert seeded == {generate_run_uuid(0, 1, subset, 1) for subset in range(len(basic.params['a']))}
+    assert seeded.isdisjoint(reseeded)
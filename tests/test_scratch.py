@@ -0,0 +1,33 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy_count(params, substep, state_history, previous_state, scratch):
+    scratch['calls'] = scratch.get('calls', 0) + 1
+    return {'calls': scratch['calls']}
+
+def update_calls(params, substep, state_history, previous_state, policy_input, scratch):
+    return 'calls', policy_input['calls']
+
+state_update_blocks = [
+    {
+        'policies': {'count': policy_count},
+        'variables': {'calls': update_calls},
+    },
+    {
+        'policies': {'count': policy_count},
+        'variables': {'calls': update_calls},
+    },
+]
+
+
+def test_scratch():
+    model = Model(initial_state={'calls': 0}, state_update_blocks=state_update_blocks, params={'a': [1, 2]})
+    simulation = Simulation(model=model, timesteps=3, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, scratch=True)
+    result = simulation.run()
+
+    # Scratch data persists across substeps and timesteps, and is reset for each run
+    for (run, subset) in [(1, 0), (1, 1), (2, 0), (2, 1)]:
+        assert [state['calls'] for state in result if state['run'] == run and state['subset'] == subset] == list(range(7))
+    assert all('scratch' not in state for state in result)
@@ -0,0 +1,80 @@
+import random
+import pytest
+
+from radcad import Model, Simulation
+from radcad.core import derive_run_seed
+from radcad.engine import Engine, Backend
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', random.random()
+
+
+model = Model(initial_state={'a': 0.0}, state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}], params={'b': [1, 2]})
+
+
+def test_seed():
+    simulation = Simulation(model=model, timesteps=3, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, seed=42)
+    result = simulation.run()
+
+    assert simulation.check_reproducible() is None
+    assert [run['seed'] for run in simulation.exceptions] == [
+        derive_run_seed(42, 0, run, subset) for run in range(2) for subset in range(2)
+    ]
+    assert len({run['seed'] for run in simulation.exceptions}) == 4
+
+    # A run replayed in isolation is seeded identically
+    replayed = simulation.run(only=[(1, 0)])
+    assert replayed == [state for state in result if (state['run'], state['subset']) == (2, 0)]
+
+
+def test_seed_disabled():
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+
+    assert 'seed' not in simulation.exceptions[0]
+
+
+def update_b(params, substep, state_history, previous_state, policy_input):
+    # Draws a varying number of random numbers, changing the draws of the following substeps unless seeded per substep
+    return 'b', [random.random() for _ in range(params['draws'])]
+
+
+def run_substeps(draws, **kwargs):
+    model = Model(
+        initial_state={'a': 0.0, 'b': []},
+        state_update_blocks=[{'policies': {}, 'variables': {'b': update_b}}, {'policies': {}, 'variables': {'a': update_a}}],
+        params={'draws': draws},
+    )
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, seed=42, **kwargs)
+    return [state['a'] for state in simulation.run() if state['substep'] == 2]
+
+
+def test_seed_substeps():
+    assert run_substeps(1, seed_substeps=True) == run_substeps(2, seed_substeps=True)
+    assert run_substeps(1) != run_substeps(2)
+
+
+def test_seed_substeps_requires_seed():
+    with pytest.raises(ValueError):
+        Engine(seed_substeps=True)
+
+
+def test_seed_substeps_threads():
+    with pytest.raises(ValueError, match="substeps"):
+        Engine(backend=Backend.THREADS, seed=42, seed_substeps=True)
+
+
+def test_seed_threads():
+    with pytest.raises(ValueError):
+        Engine(backend=Backend.THREADS, seed=42)
+
+    # The backend may be changed after the Engine is created
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    simulation.engine = Engine(seed=42)
+    simulation.engine.backend = Backend.THREADS
+    with pytest.raises(ValueError):
+        simulation.run()
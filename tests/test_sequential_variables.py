@@ -0,0 +1,33 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def policy(params, substep, state_history, previous_state):
+    return {'total': previous_state['a'] + previous_state['b']}
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+def update_b(params, substep, state_history, previous_state, policy_input):
+    return 'b', policy_input['total']
+
+def run(sequential_variables):
+    model = Model(
+        initial_state={'a': 0, 'b': 0},
+        state_update_blocks=[{
+            'policies': {'p': policy},
+            'variables': {'a': update_a, 'b': update_b},
+            'sequential_variables': sequential_variables,
+        }],
+        params={},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return [(state['a'], state['b']) for state in simulation.run()]
+
+def test_sequential_variables():
+    # Policies see the update of `a` before `b` is updated
+    assert run(True) == [(0, 0), (1, 1), (2, 3)]
+
+def test_parallel_variables():
+    assert run(False) == [(0, 0), (1, 0), (2, 1)]
@@ -0,0 +1,77 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from radcad.serializers import Serializer, PickleSerializer, DeepcopySerializer, NumpySerializer
+from tests.test_cases import basic
+
+import copy
+import pytest
+
+
+class CountingSerializer(Serializer):
+    def __init__(self):
+        self.calls = 0
+
+    def dumps(self, obj):
+        self.calls += 1
+        return copy.deepcopy(obj)
+
+    def loads(self, data):
+        return data
+
+def test_pickle_serializer_protocol():
+    serializer = PickleSerializer(protocol=2)
+    state = {'a': [1, 2, 3]}
+    assert serializer.loads(serializer.dumps(state)) == state
+
+def test_custom_serializer():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    serializer = CountingSerializer()
+    experiment = Experiment(Simulation(model=model, timesteps=10, runs=1, serializer=serializer))
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    serializer_result = experiment.run()
+
+    experiment = Experiment(Simulation(model=model, timesteps=10, runs=1, serializer=PickleSerializer(protocol=5)))
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    pickle_result = experiment.run()
+
+    assert serializer.calls == 10 * len(basic.state_update_blocks) * len(basic.params['a'])
+    assert serializer_result == pickle_result
+
+def test_pickle_serializer_out_of_band():
+    state = {'a': bytearray(b'abc'), 'b': [1, {'c': 2}]}
+    copied = PickleSerializer(protocol=5).copy(state)
+    assert copied == state
+    copied['a'][0] = 0
+    assert state['a'] == bytearray(b'abc')
+
+    numpy = pytest.importorskip("numpy")
+    state = {'a': numpy.arange(3)}
+    copied = PickleSerializer().copy(state)
+    copied['a'][0] = 10
+    assert list(state['a']) == [0, 1, 2]
+
+def test_engine_serializer():
+    with pytest.raises(Exception, match="Invalid Engine option"):
+        Engine(serializer=PickleSerializer())
+
+def test_deepcopy_serializer():
+    state = {'a': [1, 2, 3], 'f': lambda x: x}
+    copied = DeepcopySerializer().copy(state)
+    assert copied['a'] == state['a'] and copied['a'] is not state['a']
+
+def test_numpy_serializer():
+    numpy = pytest.importorskip("numpy")
+    state = {'a': numpy.arange(3), 'b': [1, 2, 3]}
+    copied = NumpySerializer().copy(state)
+    assert list(copied) == ['a', 'b']
+    assert (copied['a'] == state['a']).all() and copied['a'] is not state['a']
+    assert copied['b'] == state['b'] and copied['b'] is not state['b']
+
+def test_simulation_serializer():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    serializer = CountingSerializer()
+    simulation = Simulation(model=model, timesteps=10, runs=1, serializer=serializer)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    simulation.run()
+
+    assert serializer.calls == 10 * len(basic.state_update_blocks) * len(basic.params['a'])
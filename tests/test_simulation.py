@@ -1,6 +1,8 @@
+import json
 import pytest
 
 from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
 from tests.test_cases import basic
 
 
@@ -32,3 +34,44 @@ def test_run():
     assert len(raw_result) > 0
     assert raw_result == experiment.results
     assert simulation.run() == raw_result
+
+
+def test_result_schema():
+    model = Model(initial_state={'a': 1, 'b': 0.5, 'c': 'x', 'd': [], 'e': True})
+    simulation = Simulation(model=model, timesteps=1, runs=1, record_wall_time=True)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, run_uuid=True)
+
+    schema = simulation.result_schema()
+    assert schema == {
+        'a': int, 'b': float, 'c': str, 'd': object, 'e': bool,
+        'simulation': int, 'subset': int, 'run': int, 'run_uuid': str, 'substep': int, 'timestep': int, 'wall_time': float,
+    }
+    assert list(schema) == list(simulation.run()[0])
+
+
+def test_invalid_timesteps_runs():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    for (timesteps, runs) in [(0, 1), (-1, 1), (1.5, 1), (True, 1), (10, 0), (10, -2), (10, '3')]:
+        with pytest.raises(ValueError):
+            Simulation(model=model, timesteps=timesteps, runs=runs)
+
+
+def test_timesteps_runs_coerced():
+    np = pytest.importorskip("numpy")
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=np.int64(10), runs=np.int32(2))
+    assert type(simulation.timesteps) is int and simulation.timesteps == 10
+    assert type(simulation.runs) is int and simulation.runs == 2
+
+
+def test_run_ndjson(tmp_path):
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    expected = simulation.run()
+
+    path = tmp_path / 'results.ndjson'
+    assert simulation.run(ndjson_path=str(path)) == len(expected)
+    # States are written rather than retained
+    assert simulation.results == []
+    assert [json.loads(line) for line in path.read_text().splitlines()] == expected
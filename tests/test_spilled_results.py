@@ -0,0 +1,101 @@
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from radcad.results import SpilledResults
+from tests.test_cases import basic
+
+import copy
+import pytest
+from unittest import mock
+
+
+def test_spilled_results():
+    results = SpilledResults(spill_threshold=3)
+    states = [{'timestep': timestep} for timestep in range(10)]
+    results.extend(states)
+
+    assert len(results) == 10
+    assert len(results._buffer) == 1
+    assert list(results) == states
+    assert results.to_list() == states
+    assert results[4] == states[4]
+    assert results[-1] == states[-1]
+    assert results[2:6] == states[2:6]
+    assert copy.deepcopy(results).to_list() == states
+
+    results.close()
+    assert len(results) == 0
+
+def test_spilled_results_slice():
+    results = SpilledResults(spill_threshold=3)
+    states = [{'timestep': timestep} for timestep in range(10)]
+    results.extend(states)
+
+    with mock.patch.object(results, '_load_chunk', wraps=results._load_chunk) as load_chunk:
+        assert results[:] == states
+        # Each of the 3 spilled chunks is loaded once
+        assert load_chunk.call_count == 3
+    for index in [slice(1, 8), slice(None, None, -1), slice(8, 0, -3), slice(2, 2), slice(-4, None)]:
+        assert results[index] == states[index]
+
+def test_spill_threshold():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    experiment = Experiment(simulation)
+
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS, spill_threshold=7)
+    spilled_result = experiment.run()
+
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = experiment.run()
+
+    assert isinstance(spilled_result, SpilledResults)
+    assert len(spilled_result) == len(result)
+    assert spilled_result.to_list() == result
+
+def test_spilled_results_compression():
+    states = [{'timestep': timestep, 'agents': [{'balance': 0.0}] * 100} for timestep in range(10)]
+
+    uncompressed = SpilledResults(spill_threshold=3)
+    uncompressed.extend(states)
+    for compression in ['gzip', 'bz2', 'lzma']:
+        results = SpilledResults(spill_threshold=3, compression=compression)
+        results.extend(states)
+
+        assert list(results) == states
+        assert results[4] == states[4]
+        assert copy.deepcopy(results).compression == compression
+        assert results._file.seek(0, 2) < uncompressed._file.seek(0, 2)
+
+    with pytest.raises(ValueError, match="Compression must be one of"):
+        SpilledResults(spill_threshold=3, compression='zip')
+
+def test_spill_compression():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, spill_threshold=5, compression='gzip')
+    spilled_result = simulation.run()
+
+    assert spilled_result.compression == 'gzip'
+    assert spilled_result.to_list() == result
+
+def test_single_process_runs_collected_lazily_only_when_spilling():
+    from radcad.backends.single_process import ExecutorSingleProcess
+
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+    executed = []
+    simulation.on_run_finished = lambda simulation, run, subset, seconds: executed.append(subset)
+
+    for (spill_threshold, lazy) in [(None, False), (10, True)]:
+        executed.clear()
+        engine = Engine(backend=Backend.SINGLE_PROCESS, spill_threshold=spill_threshold)
+        engine._lazy_results = lazy
+        engine._run_generator = engine._run_stream(simulation, [Engine._get_config(simulation)])
+        result = ExecutorSingleProcess(engine).execute_runs()
+        # Runs are executed eagerly, returning a list, unless spilling
+        assert isinstance(result, list) is not lazy
+        assert executed == ([] if lazy else [0, 1])
+        assert len(list(result)) == 2
@@ -0,0 +1,42 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_start_timestep():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=10, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run()
+
+    simulation = Simulation(model=model, timesteps=10, runs=1, start_timestep=1000)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    offset_result = simulation.run()
+
+    assert [state['timestep'] for state in offset_result] == [state['timestep'] + 1000 for state in result]
+    assert [{**state, 'timestep': 0} for state in offset_result] == [{**state, 'timestep': 0} for state in result]
+    assert offset_result[-1]['timestep'] == 1010
+
+
+def test_initial_state_timestep():
+    # A timestep in the initial state takes precedence over start_timestep, and timesteps are numbered consecutively from it
+    model = Model(initial_state={**basic.states, 'timestep': 5}, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=3, runs=1, start_timestep=1000, drop_substeps=True)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert [state['timestep'] for state in simulation.run()] == [5, 6, 7, 8]
+
+    # The initial history, numbered from a non-zero timestep, is filled with the run_uuid, delta, and cumulative columns,
+    # continued by the initial state
+    initial_history = [[{'a': 1.0, 'b': 1.0, 'timestep': 5, 'substep': 0}], [{'a': 1.0, 'b': 1.5, 'timestep': 6, 'substep': 2}]]
+    simulation = Simulation(
+        model=model, timesteps=3, runs=1, drop_substeps=True, initial_history=initial_history, deltas=['b'], cumulative={'b': 'sum'}
+    )
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, run_uuid=True)
+    result = simulation.run()
+
+    assert [state['timestep'] for state in result] == [5, 6, 7, 8, 9, 10]
+    assert [state['b'] for state in result] == [1.0, 1.5, 2.0, 7.0, 12.0, 17.0]
+    assert [state['b_delta'] for state in result] == [None, 0.5, 0.5, 5.0, 5.0, 5.0]
+    assert [state['b_cumsum'] for state in result] == [1.0, 2.5, 4.5, 11.5, 23.5, 40.5]
+    assert len({state['run_uuid'] for state in result}) == 1
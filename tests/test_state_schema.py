@@ -0,0 +1,50 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+import pytest
+
+
+def update_active(params, substep, state_history, previous_state, policy_input):
+    return 'active', params['active']
+
+def update_phase(params, substep, state_history, previous_state, policy_input):
+    return 'phase', params['phase']
+
+def update_price(params, substep, state_history, previous_state, policy_input):
+    return 'price', params['price']
+
+def run(active=True, phase='bull', price=1, state_schema=None):
+    model = Model(
+        initial_state={'active': False, 'phase': 'bear', 'price': 1.0, 'count': 0},
+        state_update_blocks=[{
+            'policies': {},
+            'variables': {'active': update_active, 'phase': update_phase, 'price': update_price},
+        }],
+        params={'active': active, 'phase': phase, 'price': price},
+    )
+    simulation = Simulation(model=model, timesteps=2, runs=1, state_schema=state_schema or {
+        'active': bool,
+        'phase': {'bull', 'bear'},
+        'price': float,
+        'count': int,
+    })
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation.run()
+
+def test_state_schema():
+    assert run()[-1]['phase'] == 'bull'
+
+def test_state_schema_bool():
+    with pytest.raises(TypeError, match="Invalid type int for state variable active at timestep 1 / substep 1, expected bool"):
+        run(active=1)
+
+def test_state_schema_int():
+    with pytest.raises(TypeError, match="Invalid type bool for state variable active at timestep 0 / substep 0, expected int"):
+        run(state_schema={'active': int})
+    run(state_schema={'active': (bool, int)}, active=1)
+    with pytest.raises(TypeError, match="Invalid type bool for state variable price"):
+        run(price=True)
+
+def test_state_schema_categorical():
+    with pytest.raises(ValueError, match="Invalid value 'crab' for state variable phase at timestep 1 / substep 1, expected one of \\['bear', 'bull'\\]"):
+        run(phase='crab')
@@ -0,0 +1,66 @@
+import pytest
+
+from radcad import Model, Simulation, SimulationStepper
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def simulation(**kwargs):
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=3, runs=2, **kwargs)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation
+
+
+def test_stepper():
+    expected = [state for state in simulation().run() if (state['run'], state['subset']) == (2, 1)]
+
+    stepper = simulation().stepper(run=1, subset=1)
+    assert isinstance(stepper, SimulationStepper)
+    assert stepper.initial_state == expected[0]
+
+    states = [stepper.initial_state]
+    while not stepper.done:
+        timestep_states = stepper.step()
+        # Each step advances exactly one timestep
+        assert {state['timestep'] for state in timestep_states} == {stepper.timestep}
+        states.extend(timestep_states)
+    assert stepper.timestep == 3
+    assert states == expected
+    assert stepper.results == expected
+
+    with pytest.raises(RuntimeError):
+        stepper.step()
+
+
+def test_stepper_engine_unchanged():
+    # A stepper doesn't change the executable of an Engine shared with other runs
+    stepping_simulation = simulation()
+    other_simulation = simulation()
+    other_simulation.engine = stepping_simulation.engine
+    other_simulation.run()
+
+    stepping_simulation.stepper()
+    assert stepping_simulation.engine.executable is other_simulation
+
+
+def test_stepper_external_state():
+    # State can be exchanged with another engine between timesteps
+    stepper = simulation().stepper()
+    stepper.step()
+    stepper.step()[-1]['b'] = 0
+    assert stepper.step()[-1]['b'] == 5
+
+
+def test_stepper_terminating_condition():
+    stepper = simulation(terminating_condition=lambda state: state['timestep'] == 2).stepper()
+    stepper.step()
+    assert not stepper.done
+    stepper.step()
+    assert stepper.done
+    assert stepper.metadata['terminated_at'] == 2
+
+
+def test_stepper_invalid_run():
+    with pytest.raises(ValueError):
+        simulation().stepper(run=2)
@@ -0,0 +1,51 @@
+from radcad import Model, Simulation
+from radcad.core import STOP
+from radcad.engine import Engine, Backend
+
+
+def circuit_breaker(params, substep, state_history, previous_state):
+    return STOP if previous_state['a'] >= params['limit'] else {}
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+def update_b(params, substep, state_history, previous_state, policy_input):
+    return 'b', previous_state['b'] + 1
+
+def run(limit, sequential_variables=False):
+    model = Model(
+        initial_state={'a': 0, 'b': 0},
+        state_update_blocks=[
+            {'policies': {}, 'variables': {'a': update_a}},
+            {
+                'policies': {'breaker': circuit_breaker},
+                'variables': {'b': update_b},
+                'sequential_variables': sequential_variables,
+            },
+        ],
+        params={'limit': limit},
+    )
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, allow_stop=True)
+    return [(state['timestep'], state['substep'], state['a'], state['b']) for state in simulation.run()]
+
+def test_stop():
+    assert run(limit=2) == [
+        (0, 0, 0, 0),
+        (1, 1, 1, 0), (1, 2, 1, 1),
+        (2, 1, 2, 1),
+        (3, 1, 3, 1),
+    ]
+    assert run(limit=2, sequential_variables=True) == run(limit=2)
+
+def test_stop_first_substep():
+    model = Model(
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {'breaker': circuit_breaker}, 'variables': {'a': update_a}}],
+        params={'limit': 1},
+    )
+    simulation = Simulation(model=model, timesteps=3, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, allow_stop=True)
+    assert [(state['timestep'], state['substep'], state['a']) for state in simulation.run()] == [
+        (0, 0, 0), (1, 1, 1), (2, 0, 1), (3, 0, 1),
+    ]
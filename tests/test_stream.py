@@ -0,0 +1,80 @@
+import pytest
+
+from radcad import Model, Simulation, Experiment
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_stream():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=basic.TIMESTEPS, runs=basic.RUNS)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    stream = simulation.stream()
+    first = next(stream)
+    assert (first['timestep'], first['substep']) == (0, 0)
+
+    streamed = [first, *stream]
+    assert streamed == simulation.run()
+
+
+def test_stream_only():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    experiment = Experiment([Simulation(model=model, timesteps=1, runs=2)])
+    experiment.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    streamed = list(experiment.stream(only=[(0, 1, 0)]))
+    assert {(state['simulation'], state['run'], state['subset']) for state in streamed} == {(0, 2, 0)}
+
+
+def test_stream_exception():
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        raise Exception('Forced exception from state update function')
+
+    model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}])
+    simulation = Simulation(model=model, timesteps=1, runs=1)
+
+    stream = simulation.stream()
+    assert next(stream)['timestep'] == 0
+    with pytest.raises(Exception) as e:
+        next(stream)
+    assert str(e.value) == 'Forced exception from state update function'
+
+
+def test_stream_recorded_states():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=7, runs=1, record_when=lambda state: state['substep'] != 1)
+
+    # Streamed states are the recorded states of run(), after record_when, record_stride, and drop_substeps
+    for options in [{}, {'record_stride': 3}, {'drop_substeps': True}, {'final_only': True}]:
+        simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **options)
+        assert list(simulation.stream()) == simulation.run()
+
+    simulation.record_when = None
+    simulation.record_wall_time = True
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert all('wall_time' in state for state in simulation.stream())
+
+
+def test_stream_capture_stdout():
+    from radcad.core import RunOptions, iter_single_run
+
+    def update_a(params, substep, state_history, previous_state, policy_input):
+        print('update_a')
+        return 'a', previous_state['a'] + 1
+
+    stream = iter_single_run(
+        timesteps=2,
+        initial_state={'a': 0},
+        state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}],
+        options=RunOptions(capture_stdout=True),
+    )
+    while True:
+        try:
+            next(stream)
+            # Printed by the consumer of the stream, rather than the run
+            print('consumer')
+        except StopIteration as stop:
+            _, _, _, metadata = stop.value
+            break
+    assert metadata['stdout'] == 'update_a\n' * 2
@@ -0,0 +1,42 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import pytest
+
+
+def converged_adds_key(previous_substate, substate):
+    substate['stray'] = True
+    return True
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    return 'a', previous_state['a'] + 1
+
+initial_state = {
+    'a': 0,
+}
+
+state_update_blocks = [
+    {
+        'policies': {},
+        'variables': {
+            'a': update_a,
+        },
+        'converged': converged_adds_key,
+    },
+]
+
+def test_strict_schema():
+    model = Model(initial_state=initial_state, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=5)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, strict_schema=True)
+
+    with pytest.raises(KeyError) as e:
+        simulation.run()
+    assert e.value.args[0] == "State schema changed at timestep 2 / substep 1: added ['stray'], removed []"
+
+def test_strict_schema_valid():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params=basic.params)
+    simulation = Simulation(model=model, timesteps=5)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, strict_schema=True, run_uuid=True)
+    assert len(simulation.run()) == 2 * (1 + 5 * 2)
@@ -0,0 +1,46 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_balance(params, substep, state_history, previous_state, policy_input):
+    return 'balance', previous_state['balance'] * 1.1
+
+def update_fees(params, substep, state_history, previous_state, policy_input):
+    return 'fees', previous_state['fees'] + 1
+
+
+def run(state_update_blocks):
+    model = Model(initial_state={'balance': 100.0, 'fees': 0}, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation, simulation.run()
+
+
+def test_substep_label():
+    simulation, result = run([
+        {
+            'label': 'accrue_interest',
+            'policies': {},
+            'variables': {'balance': update_balance},
+        },
+        {
+            'policies': {},
+            'variables': {'fees': update_fees},
+        },
+    ])
+    assert [(state['substep'], state['substep_label']) for state in result] == [
+        (0, None), (1, 'accrue_interest'), (2, None), (1, 'accrue_interest'), (2, None)
+    ]
+    assert list(simulation.result_schema()) == list(result[0])
+    assert simulation.result_schema()['substep_label'] is object
+
+
+def test_no_substep_label():
+    simulation, result = run([
+        {
+            'policies': {},
+            'variables': {'balance': update_balance},
+        },
+    ])
+    assert all('substep_label' not in state for state in result)
+    assert 'substep_label' not in simulation.result_schema()
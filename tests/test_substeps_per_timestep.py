@@ -0,0 +1,30 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def run_simulation(substeps_per_timestep, **kwargs):
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': 1, 'b': 1})
+    simulation = Simulation(model=model, timesteps=2, runs=1, substeps_per_timestep=substeps_per_timestep)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation.run()
+
+
+def test_substeps_per_timestep_padding():
+    result = run_simulation(None)
+    padded = run_simulation(4)
+
+    assert [(state['timestep'], state['substep']) for state in padded] == [
+        (0, 0), (1, 1), (1, 2), (1, 3), (1, 4), (2, 1), (2, 2), (2, 3), (2, 4)
+    ]
+    # Padded substeps are copies of the final substate, and don't affect the following timesteps
+    assert padded[3] == {**result[2], 'substep': 3}
+    assert padded[8] == {**result[-1], 'substep': 4}
+
+
+def test_substeps_per_timestep_truncation():
+    result = run_simulation(None)
+    truncated = run_simulation(1)
+
+    assert [(state['timestep'], state['substep']) for state in truncated] == [(0, 0), (1, 2), (2, 2)]
+    assert truncated[-1] == result[-1]
@@ -0,0 +1,31 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def run_simulation(terminating_condition, **kwargs):
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1, 2], 'b': 1})
+    simulation = Simulation(model=model, timesteps=10, runs=1, terminating_condition=terminating_condition)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, **kwargs)
+    return simulation, simulation.run()
+
+
+def test_terminating_condition():
+    # The subset with the larger step size terminates earlier
+    simulation, result = run_simulation(lambda state: state['b'] >= 30)
+
+    assert [run['terminated_at'] for run in simulation.exceptions] == [6, 3]
+    for run in simulation.exceptions:
+        final_state = [state for state in result if state['subset'] == run['subset']][-1]
+        assert final_state['timestep'] == run['terminated_at']
+        assert final_state['b'] >= 30
+
+
+def test_terminating_condition_record_stride():
+    simulation, result = run_simulation(lambda state: state['b'] >= 30, record_stride=4)
+    assert [state['timestep'] for state in result if state['subset'] == 0 and state['substep'] in [0, 2]] == [0, 4, 6]
+
+
+def test_not_terminated():
+    simulation, result = run_simulation(lambda state: False)
+    assert all('terminated_at' not in run for run in simulation.exceptions)
@@ -0,0 +1,44 @@
+import time
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_a(params, substep, state_history, previous_state, policy_input):
+    time.sleep(0.01)
+    return 'a', previous_state['a'] + 1
+
+
+model = Model(initial_state={'a': 0}, state_update_blocks=[{'policies': {}, 'variables': {'a': update_a}}])
+
+
+def test_timeout():
+    simulation = Simulation(model=model, timesteps=20, runs=3)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run(timeout_seconds=0.05)
+
+    assert simulation.timed_out
+    # The interrupted run returns its completed timesteps, and no further runs are started
+    assert {state['run'] for state in result} == {1}
+    assert 1 < len(result) < 21
+    assert [state['a'] for state in result] == list(range(len(result)))
+    assert simulation.exceptions[0]['timed_out']
+    assert simulation.exceptions[0]['terminated_at'] == result[-1]['timestep']
+
+
+def test_timeout_before_first_run():
+    simulation = Simulation(model=model, timesteps=2, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    assert simulation.run(timeout_seconds=0) == []
+    assert simulation.exceptions == []
+    assert simulation.timed_out
+
+
+def test_no_timeout():
+    simulation = Simulation(model=model, timesteps=2, runs=2)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    result = simulation.run(timeout_seconds=60)
+
+    assert not simulation.timed_out
+    assert len(result) == 6
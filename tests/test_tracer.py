@@ -0,0 +1,41 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+
+def test_tracer():
+    events = []
+    tracer = lambda event, span, attributes: events.append((event, span, attributes))
+
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, tracer=tracer)
+    simulation.run()
+
+    assert events[0] == ('start', 'timestep', {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 0})
+    assert events[1] == ('start', 'variable', {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 1, 'name': 'a'})
+    assert events[2] == ('end', 'variable', {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 1, 'name': 'a'})
+    assert events[3] == ('start', 'policy', {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 2, 'name': 'p_1'})
+    assert events[-1] == ('end', 'timestep', {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 2, 'substep': 0})
+
+    # Per timestep: timestep span, one variable in the first substep, five policies and one variable in the second substep
+    assert len(events) == 2 * 2 * (1 + 1 + 5 + 1)
+    assert [event for (event, _, _) in events].count('start') == len(events) / 2
+
+def test_tracer_error():
+    events = []
+    tracer = lambda event, span, attributes: events.append((event, span, attributes))
+
+    def failing_policy(params, substep, state_history, previous_state):
+        raise ValueError("Failed")
+
+    state_update_blocks = [{'policies': {'p_1': failing_policy}, 'variables': {'a': basic.update_a}}]
+    model = Model(initial_state=basic.states, state_update_blocks=state_update_blocks, params={})
+    simulation = Simulation(model=model, timesteps=2, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS, tracer=tracer, raise_exceptions=False)
+    simulation.run()
+
+    assert [(event, span) for (event, span, _) in events] == [('start', 'timestep'), ('start', 'policy'), ('error', 'policy')]
+    attributes = events[-1][2]
+    assert attributes['name'] == 'p_1' and attributes['timestep'] == 1
+    assert isinstance(attributes['error'], ValueError)
@@ -1,4 +1,9 @@
-from radcad.utils import generate_cartesian_product_parameter_sweep
+from radcad.utils import diff_results, generate_cartesian_product_parameter_sweep, generate_oat_sweep, generate_sampled_sweep, merge_results, safe_div, split_indices, to_dataset_dict, write_ndjson, write_parquet
+from radcad.core import generate_parameter_sweep
+
+import json
+import math
+import pytest
 
 
 def test_generate_cartesian_product_parameter_sweep():
@@ -13,3 +18,173 @@ def test_generate_cartesian_product_parameter_sweep():
         'b': [0, 0, 0, 1, 1, 1],
         'c': [0, 1, 2, 0, 1, 2]
     }
+
+def test_generate_oat_sweep():
+    param_sweep = generate_oat_sweep({'a': 1, 'b': 0.5, 'c': 'x'}, {'a': [0, 2], 'b': [0.25]})
+    assert generate_parameter_sweep(param_sweep) == [
+        {'a': 1, 'b': 0.5, 'c': 'x'},
+        {'a': 0, 'b': 0.5, 'c': 'x'},
+        {'a': 2, 'b': 0.5, 'c': 'x'},
+        {'a': 1, 'b': 0.25, 'c': 'x'},
+    ]
+
+    with pytest.raises(KeyError):
+        generate_oat_sweep({'a': 1}, {'d': [0]})
+
+
+def test_merge_results():
+    batch_a = [
+        {'simulation': 0, 'subset': 0, 'run': 1, 'timestep': 0},
+        {'simulation': 0, 'subset': 0, 'run': 2, 'timestep': 0},
+        {'simulation': 1, 'subset': 0, 'run': 1, 'timestep': 0},
+    ]
+    batch_b = [
+        {'simulation': 0, 'subset': 0, 'run': 1, 'timestep': 0},
+        {'simulation': 2, 'subset': 0, 'run': 1, 'timestep': 0},
+    ]
+    merged = merge_results([batch_a, batch_b])
+    assert [(state['simulation'], state['run']) for state in merged] == [(0, 1), (0, 2), (1, 1), (0, 3), (2, 1)]
+    assert batch_b[0]['run'] == 1
+
+    assert merge_results([batch_a, batch_b], reindex=False) == batch_a + batch_b
+
+    # Zero-based run indices don't collide
+    zero_based = [{**state, 'run': state['run'] - 1} for state in batch_a]
+    merged = merge_results([zero_based, zero_based])
+    assert [(state['simulation'], state['run']) for state in merged] == [(0, 0), (0, 1), (1, 0), (0, 2), (0, 3), (1, 1)]
+
+def test_generate_sampled_sweep_lhs():
+    param_ranges = {'a': (0, 1), 'b': (10, 20)}
+    sweep = generate_sampled_sweep(param_ranges, method='lhs', n_samples=5, seed=1)
+
+    assert sweep == generate_sampled_sweep(param_ranges, method='lhs', n_samples=5, seed=1)
+    assert len(generate_parameter_sweep(sweep)) == 5
+    # Each of the n_samples strata of each parameter range is sampled exactly once
+    assert sorted(int(a * 5) for a in sweep['a']) == [0, 1, 2, 3, 4]
+    assert sorted(int((b - 10) / 2) for b in sweep['b']) == [0, 1, 2, 3, 4]
+
+def test_generate_sampled_sweep_sobol():
+    sweep = generate_sampled_sweep({'a': (0, 1), 'b': (0, 1), 'c': (0, 2)}, method='sobol', n_samples=4)
+    assert sweep == {
+        'a': [0.0, 0.5, 0.75, 0.25],
+        'b': [0.0, 0.5, 0.25, 0.75],
+        'c': [0.0, 1.0, 0.5, 1.5],
+    }
+
+    scrambled_sweep = generate_sampled_sweep({'a': (0, 1)}, method='sobol', n_samples=4, seed=1)
+    assert scrambled_sweep == generate_sampled_sweep({'a': (0, 1)}, method='sobol', n_samples=4, seed=1)
+    assert sorted(int(a * 4) for a in scrambled_sweep['a']) == [0, 1, 2, 3]
+
+def test_generate_sampled_sweep_invalid_method():
+    with pytest.raises(ValueError):
+        generate_sampled_sweep({'a': (0, 1)}, method='grid')
+
+def test_to_dataset_dict():
+    results = [
+        {'a': 1.0, 'b': 'x', 'simulation': 0, 'subset': subset, 'run': run, 'substep': substep, 'timestep': timestep}
+        for run in [1, 2]
+        for subset in [0, 1, 2]
+        for (timestep, substep) in [(0, 0), (1, 1), (1, 2), (2, 1), (2, 2)]
+    ]
+    dataset = to_dataset_dict(results)
+
+    assert dataset['dims'] == {'run': 2, 'subset': 3, 'timestep': 3, 'substep': 3}
+    assert dataset['coords']['run'] == {'dims': 'run', 'data': [1, 2]}
+    assert list(dataset['data_vars']) == ['a']
+    a = dataset['data_vars']['a']
+    assert a['dims'] == ('run', 'subset', 'timestep', 'substep')
+    assert a['data'][1][2][2][2] == 1.0
+    assert math.isnan(a['data'][0][0][0][1])
+
+def test_to_dataset_dict_non_uniform():
+    results = [
+        {'a': 1.0, 'simulation': 0, 'subset': 0, 'run': 1, 'substep': 0, 'timestep': 0},
+        {'a': 1.0, 'simulation': 0, 'subset': 0, 'run': 1, 'substep': 1, 'timestep': 1},
+        {'a': 1.0, 'simulation': 0, 'subset': 0, 'run': 2, 'substep': 0, 'timestep': 0},
+    ]
+    assert to_dataset_dict(results) == results
+
+
+def test_safe_div():
+    assert safe_div(1, 2) == 0.5
+    assert safe_div(1, 0) == 0
+    assert safe_div(1, 0.0, default=None) is None
+
+
+def test_safe_div_numpy():
+    numpy = pytest.importorskip('numpy')
+    result = safe_div(numpy.array([1, 2, 3]), numpy.array([2, 0, 3]), default=-1)
+    assert result.tolist() == [0.5, -1.0, 1.0]
+
+
+def test_write_parquet(tmp_path):
+    pyarrow_parquet = pytest.importorskip('pyarrow.parquet')
+    results = [
+        {'a': 1, 'b': 'x', 'timestep': 0},
+        {'a': 2.5, 'b': 'y', 'timestep': 1, 'c': True},
+    ]
+    path = tmp_path / 'results.parquet'
+    write_parquet(results, str(path), {'a': int, 'b': str, 'timestep': int})
+
+    table = pyarrow_parquet.read_table(str(path))
+    assert table.column_names == ['a', 'b', 'timestep', 'c']
+    assert table.to_pydict() == {'a': [1.0, 2.5], 'b': ['x', 'y'], 'timestep': [0, 1], 'c': [None, True]}
+
+
+def test_write_ndjson(tmp_path):
+    results = [
+        {'a': 1, 'b': 'x', 'timestep': 0},
+        {'a': 2.5, 'b': None, 'timestep': 1, 'c': [True]},
+    ]
+    path = tmp_path / 'results.ndjson'
+    # Results are consumed lazily
+    assert write_ndjson(iter(results), str(path)) == 2
+    assert [json.loads(line) for line in path.read_text().splitlines()] == results
+
+
+def test_write_ndjson_numpy(tmp_path):
+    np = pytest.importorskip('numpy')
+    path = tmp_path / 'results.ndjson'
+    write_ndjson([{'a': np.float64(0.5), 'b': np.arange(3)}], str(path))
+    assert json.loads(path.read_text()) == {'a': 0.5, 'b': [0, 1, 2]}
+
+
+def test_diff_results():
+    a = [
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 0, 'substep': 0, 'x': 1, 'label': 'a'},
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 1, 'x': 2, 'label': 'a'},
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 2, 'substep': 1, 'x': 3, 'label': 'a'},
+    ]
+    b = [
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 1, 'substep': 1, 'x': 2.5, 'label': 'b'},
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 0, 'substep': 0, 'x': 1, 'label': 'a'},
+        {'simulation': 0, 'run': 1, 'subset': 0, 'timestep': 3, 'substep': 1, 'x': 4, 'label': 'a'},
+    ]
+
+    diff = diff_results(a, b)
+    assert diff['columns'] == {
+        'x': {'differing': 1, 'max_delta': 0.5, 'mean_delta': 0.25},
+        'label': {'differing': 1},
+    }
+    assert diff['first_divergence'] == {'index': (0, 1, 0, 1, 1), 'keys': ['x', 'label']}
+    assert diff['only_a'] == [(0, 1, 0, 2, 1)]
+    assert diff['only_b'] == [(0, 1, 0, 3, 1)]
+
+    assert diff_results(a, a, keys=['x'])['first_divergence'] is None
+
+
+def test_split_indices():
+    results = [
+        {'x': 1, 'simulation': 0, 'subset': 0, 'run': 1, 'substep': 0, 'timestep': 0},
+        {'x': 2, 'simulation': 0, 'subset': 0, 'run': 1, 'substep': 1, 'timestep': 1},
+    ]
+    indices, states = split_indices(results)
+    assert indices == [
+        {'simulation': 0, 'subset': 0, 'run': 1, 'timestep': 0, 'substep': 0},
+        {'simulation': 0, 'subset': 0, 'run': 1, 'timestep': 1, 'substep': 1},
+    ]
+    assert states == [{'x': 1}, {'x': 2}]
+
+    indices, states = split_indices(results, index_keys=('run', 'timestep'))
+    assert indices == [{'run': 1, 'timestep': 0}, {'run': 1, 'timestep': 1}]
+    assert states[1] == {'x': 2, 'simulation': 0, 'subset': 0, 'substep': 1}
@@ -0,0 +1,46 @@
+import pytest
+
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+
+
+def update_balance(params, substep, state_history, previous_state, policy_input):
+    return 'balance', previous_state['balance'] - 4
+
+
+def update_label(params, substep, state_history, previous_state, policy_input):
+    return 'label', f"balance {previous_state['balance']}"
+
+
+def reject_negative(value):
+    if value < 0:
+        raise ValueError(f"Negative balance {value}")
+    return value
+
+
+def run_simulation(validators, sequential_variables=False):
+    model = Model(
+        initial_state={'balance': 10, 'label': ''},
+        state_update_blocks=[{
+            'policies': {},
+            'variables': {'balance': update_balance, 'label': update_label},
+            'sequential_variables': sequential_variables,
+        }],
+    )
+    simulation = Simulation(model=model, timesteps=4, runs=1, validators=validators)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    return simulation.run()
+
+
+def test_validators_clamp():
+    result = run_simulation({'balance': lambda value: max(value, 0)})
+    assert [state['balance'] for state in result] == [10, 6, 2, 0, 0]
+
+    # Sequential State Update Functions see the validated value
+    result = run_simulation({'balance': lambda value: max(value, 0)}, sequential_variables=True)
+    assert [state['label'] for state in result] == ['', 'balance 6', 'balance 2', 'balance 0', 'balance 0']
+
+
+def test_validators_reject():
+    with pytest.raises(ValueError, match="Validation of state variable balance failed at timestep 3 / substep 1: Negative balance -2"):
+        run_simulation({'balance': reject_negative})
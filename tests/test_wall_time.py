@@ -0,0 +1,35 @@
+from radcad import Model, Simulation
+from radcad.engine import Engine, Backend
+from tests.test_cases import basic
+
+import time
+
+
+def test_record_wall_time():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=5, runs=1, record_wall_time=True)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    start = time.time()
+    result = simulation.run()
+
+    wall_times = [state['wall_time'] for state in result]
+    assert all(start <= wall_time <= time.time() for wall_time in wall_times)
+    assert wall_times == sorted(wall_times)
+    # Substeps of the same timestep share a single clock read
+    assert result[1]['wall_time'] == result[2]['wall_time']
+
+def test_record_wall_time_monotonic():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=5, runs=1, record_wall_time="monotonic")
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+
+    start = time.monotonic()
+    result = simulation.run()
+    assert all(start <= state['wall_time'] <= time.monotonic() for state in result)
+
+def test_record_wall_time_disabled():
+    model = Model(initial_state=basic.states, state_update_blocks=basic.state_update_blocks, params={'a': [1], 'b': [1]})
+    simulation = Simulation(model=model, timesteps=5, runs=1)
+    simulation.engine = Engine(backend=Backend.SINGLE_PROCESS)
+    assert all('wall_time' not in state for state in simulation.run())